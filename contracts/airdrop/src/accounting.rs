@@ -0,0 +1,101 @@
+use cosmwasm_std::{Coin, Deps, Env, StdError, StdResult, Storage, Uint128};
+use cw20::{BalanceResponse, Cw20QueryMsg};
+
+use crate::allocation::amount_of;
+use crate::state::{
+    Config, TokenType, ACCOUNTING, BONUS_POOL, DAILY_CLAIMS, ESCROWED, GAS_POOL, SECONDS_PER_DAY,
+    STATS,
+};
+
+// Balance the contract holds of `denom`, whether native or a CW20 token
+pub fn query_balance(deps: Deps, env: &Env, config: &Config, denom: &str) -> StdResult<Uint128> {
+    match config.token_type {
+        TokenType::Cw20 => {
+            let balance: BalanceResponse = deps.querier.query_wasm_smart(
+                denom,
+                &Cw20QueryMsg::Balance {
+                    address: env.contract.address.to_string(),
+                },
+            )?;
+            Ok(balance.balance)
+        }
+        TokenType::Native => Ok(deps
+            .querier
+            .query_balance(env.contract.address.clone(), denom)?
+            .amount),
+    }
+}
+
+// Part of the `denom` balance set aside for escrowed tranches and the gas
+// and bonus pools, which allocations must leave alone
+pub fn reserved_balance(storage: &dyn Storage, config: &Config, denom: &str) -> StdResult<Uint128> {
+    let mut reserved = Uint128::zero();
+    if denom == config.denom {
+        reserved += ESCROWED.may_load(storage)?.unwrap_or_default();
+        reserved += BONUS_POOL.may_load(storage)?.unwrap_or_default();
+    }
+    if config.gas_pool_denom.as_deref() == Some(denom) {
+        reserved += GAS_POOL.may_load(storage)?.unwrap_or_default();
+    }
+
+    Ok(reserved)
+}
+
+// Makes sure a claim paying out `payout`, fee refund included, stays within
+// the registered total and the balance not already reserved
+pub fn assert_solvent(deps: Deps, env: &Env, config: &Config, payout: &[Coin]) -> StdResult<()> {
+    let accounting = ACCOUNTING.may_load(deps.storage)?.unwrap_or_default();
+    if let Some(total_amount) = accounting.total_amount {
+        let paid = accounting.claimed + accounting.refunded + amount_of(payout, &config.denom);
+        if paid > total_amount {
+            return Err(StdError::generic_err(
+                "claim exceeds the registered total_amount",
+            ));
+        }
+    }
+
+    for coin in payout {
+        let available = query_balance(deps, env, config, &coin.denom)?
+            .saturating_sub(reserved_balance(deps.storage, config, &coin.denom)?);
+        if coin.amount > available {
+            return Err(StdError::generic_err(format!(
+                "insufficient contract balance: need {}{}, have {}{}",
+                coin.amount, coin.denom, available, coin.denom
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+// Updates the accounting and the campaign statistics with a claim made at
+// `time`, amounts being in `denom` except for the `gas_refunded` paid by
+// the gas pool. The liquid and vesting amounts include the bonus, which is
+// paid by the bonus pool rather than the allocations
+pub fn record_claim(
+    storage: &mut dyn Storage,
+    time: u64,
+    liquid: Uint128,
+    vesting: Uint128,
+    bonus: Uint128,
+    refunded: Uint128,
+    gas_refunded: Uint128,
+) -> StdResult<()> {
+    let mut accounting = ACCOUNTING.may_load(storage)?.unwrap_or_default();
+    accounting.claimed += liquid + vesting - bonus;
+    accounting.refunded += refunded;
+    ACCOUNTING.save(storage, &accounting)?;
+
+    let mut stats = STATS.may_load(storage)?.unwrap_or_default();
+    stats.claims += 1;
+    stats.liquid += liquid;
+    stats.vesting += vesting;
+    stats.fee_refunds += refunded;
+    stats.bonus += bonus;
+    stats.gas_refunds += gas_refunded;
+    STATS.save(storage, &stats)?;
+
+    let day = time / SECONDS_PER_DAY;
+    let claims = DAILY_CLAIMS.may_load(storage, day)?.unwrap_or_default();
+    DAILY_CLAIMS.save(storage, day, &(claims + 1))
+}
@@ -0,0 +1,67 @@
+use cosmwasm_std::{StdError, StdResult};
+use sha3::{Digest, Keccak256};
+
+use crate::crypto::decode_address;
+
+// Canonical form of an address, used for every storage key and comparison.
+// The family is told apart by shape:
+// - hex EVM addresses are lowercased, after checking their EIP-55 checksum
+//   if they carry one
+// - bech32 addresses are lowercased
+// - anything else must be base58 (solana public keys) and is kept as is,
+//   base58 being case sensitive
+pub fn normalize_address(address: &str) -> StdResult<String> {
+    if address.starts_with("0x") || address.starts_with("0X") {
+        return normalize_evm(address);
+    }
+    if let Ok((hrp, data, variant)) = bech32::decode(address) {
+        return bech32::encode(&hrp, data, variant)
+            .map_err(|_| StdError::generic_err("bech32 encoding failed"));
+    }
+    if bs58::decode(address).into_vec().is_ok() {
+        return Ok(address.to_string());
+    }
+
+    Err(StdError::generic_err(format!(
+        "invalid address: {}",
+        address
+    )))
+}
+
+fn normalize_evm(address: &str) -> StdResult<String> {
+    let hex_part = &address[2..];
+    let lowercase = format!("0x{}", hex_part.to_lowercase());
+    decode_address(&lowercase)?;
+
+    // All lowercase or all uppercase addresses carry no checksum
+    let is_mixed_case = hex_part.chars().any(|c| c.is_ascii_lowercase())
+        && hex_part.chars().any(|c| c.is_ascii_uppercase());
+    if is_mixed_case && to_checksum_address(&lowercase) != format!("0x{}", hex_part) {
+        return Err(StdError::generic_err(format!(
+            "invalid EIP-55 checksum: {}",
+            address
+        )));
+    }
+
+    Ok(lowercase)
+}
+
+// EIP-55 encoding of a lowercase 0x address
+pub fn to_checksum_address(address: &str) -> String {
+    let hex_part = &address[2..];
+    let hash = Keccak256::digest(hex_part.as_bytes());
+    let checksummed: String = hex_part
+        .chars()
+        .enumerate()
+        .map(|(i, c)| {
+            let nibble = (hash[i / 2] >> (if i % 2 == 0 { 4 } else { 0 })) & 0x0f;
+            if nibble >= 8 {
+                c.to_ascii_uppercase()
+            } else {
+                c
+            }
+        })
+        .collect();
+
+    format!("0x{}", checksummed)
+}
@@ -0,0 +1,177 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use cosmwasm_std::{Coin, StdError, StdResult, Storage, Uint128};
+
+use crate::state::{Config, VestingMode, MAX_VESTING_DURATION, MAX_VESTING_PERIODS, SCHEDULES};
+
+pub fn validate_vesting_schedule(periods: &[i64], cliff_seconds: u64) -> StdResult<()> {
+    if periods.len() > MAX_VESTING_PERIODS {
+        return Err(StdError::generic_err(format!(
+            "too many vesting periods, maximum is {}",
+            MAX_VESTING_PERIODS
+        )));
+    }
+    for period in periods.iter() {
+        if *period < 0 {
+            return Err(StdError::generic_err("periods must be greater than 0"));
+        }
+    }
+
+    if cliff_seconds > 0 && periods.is_empty() {
+        return Err(StdError::generic_err(
+            "cliff requires at least one vesting period",
+        ));
+    }
+    let duration = periods
+        .iter()
+        .fold(cliff_seconds, |acc, p| acc.saturating_add(*p as u64));
+    if duration > MAX_VESTING_DURATION {
+        return Err(StdError::generic_err(format!(
+            "vesting schedule too long, maximum is {} seconds",
+            MAX_VESTING_DURATION
+        )));
+    }
+
+    Ok(())
+}
+
+// Continuous accounts unlock linearly from the block time they are created
+// at, so a cliff would only push out the end of the schedule while tokens
+// still unlock right away
+pub fn validate_continuous_cliff(vesting_mode: &VestingMode, cliff_seconds: u64) -> StdResult<()> {
+    if *vesting_mode == VestingMode::Continuous && cliff_seconds > 0 {
+        return Err(StdError::generic_err(
+            "continuous vesting does not support a cliff",
+        ));
+    }
+
+    Ok(())
+}
+
+// Parses a leaf amount into coins of the campaign denoms, sorted by denom as
+// the bank module expects. Amounts are separated by '|' in the order of
+// `Config::denoms`, and denoms left out at the end get nothing
+pub fn parse_coins(config: &Config, amount: &str) -> StdResult<Vec<Coin>> {
+    let amounts: Vec<&str> = amount.split('|').collect();
+    if amounts.len() > config.extra_denoms.len() + 1 {
+        return Err(StdError::generic_err(format!(
+            "too many denoms in claim amount, expected {}",
+            config.extra_denoms.len() + 1
+        )));
+    }
+
+    let mut coins: Vec<Coin> = vec![];
+    for (denom, amount) in config.denoms().zip(amounts) {
+        let amount = Uint128::from_str(amount)?;
+        if !amount.is_zero() {
+            coins.push(Coin {
+                denom: denom.clone(),
+                amount,
+            });
+        }
+    }
+    coins.sort_by(|a, b| a.denom.cmp(&b.denom));
+
+    Ok(coins)
+}
+
+// Adds up coins by denom, sorted by denom
+pub fn sum_coins<'a>(coins: impl Iterator<Item = &'a Coin>) -> Vec<Coin> {
+    let mut totals: BTreeMap<&str, Uint128> = BTreeMap::new();
+    for coin in coins {
+        *totals.entry(&coin.denom).or_default() += coin.amount;
+    }
+
+    totals
+        .into_iter()
+        .map(|(denom, amount)| Coin {
+            denom: denom.to_string(),
+            amount,
+        })
+        .collect()
+}
+
+pub fn amount_of(coins: &[Coin], denom: &str) -> Uint128 {
+    coins
+        .iter()
+        .filter(|c| c.denom == denom)
+        .map(|c| c.amount)
+        .sum()
+}
+
+// Parses the vesting tranches that follow amount0 in a leaf into
+// (period length, coins) pairs. The tranches take one of three forms:
+//   a1,...,aN             lengths from the global config
+//   @id,a1,...,aN         lengths from a schedule registered by the admin
+//   l1:a1,...,lN:aN       lengths carried in the leaf itself
+pub fn parse_vesting_tranches<'a>(
+    storage: &dyn Storage,
+    config: &Config,
+    values: impl Iterator<Item = &'a str>,
+) -> StdResult<Vec<(i64, Vec<Coin>)>> {
+    parse_tranche_amounts(storage, config, values)?
+        .into_iter()
+        .map(|(length, amount)| Ok((length, parse_coins(config, &amount)?)))
+        .collect()
+}
+
+fn parse_tranche_amounts<'a>(
+    storage: &dyn Storage,
+    config: &Config,
+    values: impl Iterator<Item = &'a str>,
+) -> StdResult<Vec<(i64, String)>> {
+    let values: Vec<&str> = values.collect();
+
+    if let Some(id) = values.first().and_then(|v| v.strip_prefix('@')) {
+        let schedule = SCHEDULES
+            .may_load(storage, id)?
+            .ok_or_else(|| StdError::generic_err(format!("unknown vesting schedule: {}", id)))?;
+        return zip_amounts(&schedule.periods, schedule.cliff_seconds, &values[1..]);
+    }
+
+    if values.iter().any(|v| v.contains(':')) {
+        let mut tranches: Vec<(i64, String)> = vec![];
+        for (i, value) in values.iter().enumerate() {
+            let (length, amount) = value.split_once(':').ok_or_else(|| {
+                StdError::generic_err(format!("unable to parse vesting period {}", i + 1))
+            })?;
+            let length = length.parse::<i64>().map_err(|_| {
+                StdError::generic_err(format!("unable to parse vesting period {}", i + 1))
+            })?;
+            tranches.push((length, amount.to_string()));
+        }
+        let lengths: Vec<i64> = tranches.iter().map(|t| t.0).collect();
+        validate_vesting_schedule(&lengths, 0)?;
+        return Ok(tranches);
+    }
+
+    zip_amounts(&config.vesting_periods, config.cliff_seconds, &values)
+}
+
+fn zip_amounts(
+    periods: &[i64],
+    cliff_seconds: u64,
+    amounts: &[&str],
+) -> StdResult<Vec<(i64, String)>> {
+    let mut tranches: Vec<(i64, String)> = vec![];
+    for (i, length) in periods.iter().enumerate() {
+        let amount = amounts.get(i).ok_or_else(|| {
+            StdError::generic_err(format!("unable to parse claim amount {}", i + 1))
+        })?;
+        tranches.push((*length, amount.to_string()));
+    }
+    if amounts.len() > periods.len() {
+        return Err(StdError::generic_err(format!(
+            "too many claim amounts, expected {}",
+            periods.len()
+        )));
+    }
+
+    // Nothing unlocks before the cliff, so it delays the first tranche
+    if let Some(first) = tranches.first_mut() {
+        first.0 += cliff_seconds as i64;
+    }
+
+    Ok(tranches)
+}
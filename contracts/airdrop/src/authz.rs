@@ -0,0 +1,348 @@
+// This file is generated by rust-protobuf 3.0.3. Do not edit
+// .proto file is parsed by protoc --rust-out=...
+// @generated
+
+// https://github.com/rust-lang/rust-clippy/issues/702
+#![allow(unknown_lints)]
+#![allow(clippy::all)]
+
+#![allow(unused_attributes)]
+#![cfg_attr(rustfmt, rustfmt::skip)]
+
+#![allow(dead_code)]
+#![allow(missing_docs)]
+#![allow(non_camel_case_types)]
+#![allow(non_snake_case)]
+#![allow(non_upper_case_globals)]
+#![allow(trivial_casts)]
+#![allow(unused_results)]
+#![allow(unused_mut)]
+
+//! Generated file from `authz.proto`
+
+/// Generated files are compatible only with the same version
+/// of protobuf runtime.
+const _PROTOBUF_VERSION_CHECK: () = ::protobuf::VERSION_3_0_3;
+
+///  Any is google.protobuf.Any, declared here to keep the generated code
+///  self-contained.
+#[derive(PartialEq,Clone,Default,Debug)]
+// @@protoc_insertion_point(message:cosmos.authz.v1beta1.Any)
+pub struct Any {
+    // message fields
+    // @@protoc_insertion_point(field:cosmos.authz.v1beta1.Any.type_url)
+    pub type_url: ::std::string::String,
+    // @@protoc_insertion_point(field:cosmos.authz.v1beta1.Any.value)
+    pub value: ::std::vec::Vec<u8>,
+    // special fields
+    // @@protoc_insertion_point(special_field:cosmos.authz.v1beta1.Any.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a Any {
+    fn default() -> &'a Any {
+        <Any as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl Any {
+    pub fn new() -> Any {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(2);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "type_url",
+            |m: &Any| { &m.type_url },
+            |m: &mut Any| { &mut m.type_url },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "value",
+            |m: &Any| { &m.value },
+            |m: &mut Any| { &mut m.value },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<Any>(
+            "Any",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for Any {
+    const NAME: &'static str = "Any";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                10 => {
+                    self.type_url = is.read_string()?;
+                },
+                18 => {
+                    self.value = is.read_bytes()?;
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        if !self.type_url.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.type_url);
+        }
+        if !self.value.is_empty() {
+            my_size += ::protobuf::rt::bytes_size(2, &self.value);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if !self.type_url.is_empty() {
+            os.write_string(1, &self.type_url)?;
+        }
+        if !self.value.is_empty() {
+            os.write_bytes(2, &self.value)?;
+        }
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> Any {
+        Any::new()
+    }
+
+    fn clear(&mut self) {
+        self.type_url.clear();
+        self.value.clear();
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static Any {
+        static instance: Any = Any {
+            type_url: ::std::string::String::new(),
+            value: ::std::vec::Vec::new(),
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for Any {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("Any").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for Any {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Any {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+///  MsgExec attempts to execute the provided messages using authorizations
+///  granted to the grantee.
+#[derive(PartialEq,Clone,Default,Debug)]
+// @@protoc_insertion_point(message:cosmos.authz.v1beta1.MsgExec)
+pub struct MsgExec {
+    // message fields
+    // @@protoc_insertion_point(field:cosmos.authz.v1beta1.MsgExec.grantee)
+    pub grantee: ::std::string::String,
+    // @@protoc_insertion_point(field:cosmos.authz.v1beta1.MsgExec.msgs)
+    pub msgs: ::std::vec::Vec<Any>,
+    // special fields
+    // @@protoc_insertion_point(special_field:cosmos.authz.v1beta1.MsgExec.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a MsgExec {
+    fn default() -> &'a MsgExec {
+        <MsgExec as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl MsgExec {
+    pub fn new() -> MsgExec {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(2);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "grantee",
+            |m: &MsgExec| { &m.grantee },
+            |m: &mut MsgExec| { &mut m.grantee },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_vec_simpler_accessor::<_, _>(
+            "msgs",
+            |m: &MsgExec| { &m.msgs },
+            |m: &mut MsgExec| { &mut m.msgs },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<MsgExec>(
+            "MsgExec",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for MsgExec {
+    const NAME: &'static str = "MsgExec";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                10 => {
+                    self.grantee = is.read_string()?;
+                },
+                18 => {
+                    self.msgs.push(is.read_message()?);
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        if !self.grantee.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.grantee);
+        }
+        for value in &self.msgs {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(len) + len;
+        };
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if !self.grantee.is_empty() {
+            os.write_string(1, &self.grantee)?;
+        }
+        for v in &self.msgs {
+            ::protobuf::rt::write_message_field_with_cached_size(2, v, os)?;
+        };
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> MsgExec {
+        MsgExec::new()
+    }
+
+    fn clear(&mut self) {
+        self.grantee.clear();
+        self.msgs.clear();
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static MsgExec {
+        static instance: MsgExec = MsgExec {
+            grantee: ::std::string::String::new(),
+            msgs: ::std::vec::Vec::new(),
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for MsgExec {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("MsgExec").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for MsgExec {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for MsgExec {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+static file_descriptor_proto_data: &'static [u8] = b"\
+    \n\x0bauthz.proto\x12\x14cosmos.authz.v1beta1\"6\n\x03Any\x12\x19\n\x08t\
+    ype_url\x18\x01\x20\x01(\tR\x07typeUrl\x12\x14\n\x05value\x18\x02\x20\
+    \x01(\x0cR\x05value\"R\n\x07MsgExec\x12\x18\n\x07grantee\x18\x01\x20\x01\
+    (\tR\x07grantee\x12-\n\x04msgs\x18\x02\x20\x03(\x0b2\x19.cosmos.authz.v1\
+    beta1.AnyR\x04msgsb\x06proto3\
+";
+
+/// `FileDescriptorProto` object which was a source for this generated file
+fn file_descriptor_proto() -> &'static ::protobuf::descriptor::FileDescriptorProto {
+    static file_descriptor_proto_lazy: ::protobuf::rt::Lazy<::protobuf::descriptor::FileDescriptorProto> = ::protobuf::rt::Lazy::new();
+    file_descriptor_proto_lazy.get(|| {
+        ::protobuf::Message::parse_from_bytes(file_descriptor_proto_data).unwrap()
+    })
+}
+
+/// `FileDescriptor` object which allows dynamic access to files
+pub fn file_descriptor() -> &'static ::protobuf::reflect::FileDescriptor {
+    static generated_file_descriptor_lazy: ::protobuf::rt::Lazy<::protobuf::reflect::GeneratedFileDescriptor> = ::protobuf::rt::Lazy::new();
+    static file_descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::FileDescriptor> = ::protobuf::rt::Lazy::new();
+    file_descriptor.get(|| {
+        let generated_file_descriptor = generated_file_descriptor_lazy.get(|| {
+            let mut deps = ::std::vec::Vec::with_capacity(0);
+            let mut messages = ::std::vec::Vec::with_capacity(2);
+            messages.push(Any::generated_message_descriptor_data());
+            messages.push(MsgExec::generated_message_descriptor_data());
+            let mut enums = ::std::vec::Vec::with_capacity(0);
+            ::protobuf::reflect::GeneratedFileDescriptor::new_generated(
+                file_descriptor_proto(),
+                deps,
+                messages,
+                enums,
+            )
+        });
+        ::protobuf::reflect::FileDescriptor::new_generated_2(generated_file_descriptor)
+    })
+}
@@ -0,0 +1,80 @@
+use cosmwasm_std::{Coin, Decimal, StdError, StdResult, Storage, Uint128};
+
+use crate::allocation::amount_of;
+use crate::state::{ClaimBonus, Config, BONUS_POOL};
+
+pub fn validate_claim_bonus(bonus: &ClaimBonus) -> StdResult<()> {
+    if bonus.multiplier < Decimal::one() {
+        return Err(StdError::generic_err(
+            "claim bonus multiplier must be at least 1",
+        ));
+    }
+    if bonus.start_time >= bonus.end_time {
+        return Err(StdError::generic_err(
+            "claim bonus start_time must be before end_time",
+        ));
+    }
+
+    Ok(())
+}
+
+// Multiplier a claim made at `time` gets, before the pool cap
+pub fn bonus_multiplier(config: &Config, time: u64) -> Decimal {
+    Decimal::one() + bonus_rate(config, time)
+}
+
+// Share of the allocation added on top of it at `time`
+fn bonus_rate(config: &Config, time: u64) -> Decimal {
+    match &config.claim_bonus {
+        Some(bonus) if time < bonus.end_time => {
+            let elapsed = time.saturating_sub(bonus.start_time);
+            let duration = bonus.end_time - bonus.start_time;
+            (bonus.multiplier - Decimal::one())
+                * Decimal::from_ratio(duration - elapsed.min(duration), duration)
+        }
+        _ => Decimal::zero(),
+    }
+}
+
+// Scales the `denom` amounts of the liquid part and of every tranche by the
+// bonus at `time`. When the pool can't cover the full bonus, the rate is cut
+// down to what it holds. Returns the multiplier applied and the bonus paid
+pub fn apply_bonus(
+    storage: &dyn Storage,
+    config: &Config,
+    time: u64,
+    vested: &mut [Coin],
+    vesting_periods: &mut [(i64, Vec<Coin>)],
+) -> StdResult<(Decimal, Uint128)> {
+    let mut rate = bonus_rate(config, time);
+    if rate.is_zero() {
+        return Ok((Decimal::one(), Uint128::zero()));
+    }
+
+    let base = amount_of(vested, &config.denom)
+        + vesting_periods
+            .iter()
+            .map(|(_, coins)| amount_of(coins, &config.denom))
+            .sum::<Uint128>();
+    let pool = BONUS_POOL.may_load(storage)?.unwrap_or_default();
+    if base.is_zero() || pool.is_zero() {
+        return Ok((Decimal::one(), Uint128::zero()));
+    }
+    if base * rate > pool {
+        rate = Decimal::from_ratio(pool, base);
+    }
+
+    let mut bonus = Uint128::zero();
+    let coins = vested.iter_mut().chain(
+        vesting_periods
+            .iter_mut()
+            .flat_map(|(_, coins)| coins.iter_mut()),
+    );
+    for coin in coins.filter(|c| c.denom == config.denom) {
+        let extra = coin.amount * rate;
+        coin.amount += extra;
+        bonus += extra;
+    }
+
+    Ok((Decimal::one() + rate, bonus))
+}
@@ -2,21 +2,55 @@
 use cosmwasm_std::entry_point;
 
 use cosmwasm_std::{
-    to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult, Uint128,
+    coins, from_binary, to_binary, BankMsg, Binary, Coin, Decimal, Deps, DepsMut, Env, Event,
+    MessageInfo, Order, Reply, Response, StdError, StdResult, SubMsg, SubMsgResult, Uint128,
 };
 
+use crate::accounting::{assert_solvent, query_balance, record_claim, reserved_balance};
+use crate::address::normalize_address;
+use crate::allocation::{
+    amount_of, parse_coins, parse_vesting_tranches, sum_coins, validate_continuous_cliff,
+    validate_vesting_schedule,
+};
+use crate::bonus::{apply_bonus, bonus_multiplier, validate_claim_bonus};
+use crate::escrow::record_schedule;
+use crate::migrations::{migrate_state, CONTRACT_NAME, CONTRACT_VERSION};
 use crate::msg::{
-    ConfigResponse, ExecuteMsg, InstantiateMsg, IsClaimedResponse, MerkleRootResponse, MigrateMsg,
-    QueryMsg,
+    AreClaimedResponse, BlocklistResponse, ClaimBonusResponse, ClaimRecordResponse,
+    ClaimStatusResponse, ClaimsResponse, ConfigResponse, Cw20HookMsg, DailyClaimsResponse,
+    ExecuteMsg, GasPoolResponse, InstantiateMsg, IsClaimedResponse, MerkleRootResponse, MigrateMsg,
+    PendingAdminResponse, QueryMsg, RoleResponse, RolesResponse, ScheduleResponse,
+    SimulateClaimResponse, SolvencyResponse, StatsResponse, ValidatorsResponse,
+    VestingPeriodResponse, VestingStatusResponse,
+};
+use crate::pause::{assert_not_paused, pause_state, PauseState};
+use crate::roles::assert_role;
+use crate::screening::assert_not_blocked;
+use crate::state::{
+    ClaimRecord, ClawbackInfo, Config, PauseInfo, PendingAdmin, PendingSweep, PendingVesting, Role,
+    Schedule, SweepDestination, TokenType, VestingFallback, VestingMode, ACCOUNTING, BLOCKLIST,
+    BONUS_POOL, CLAIM_INDEX, CLAIM_RECORDS, CONFIG, DAILY_CLAIMS, ESCROWED, GAS_POOL, MERKLE_ROOT,
+    PAUSE, PENDING_ADMIN, PENDING_SWEEP, PENDING_VESTING, RECIPIENT_CLAIMS, ROLES, SCHEDULES,
+    SECONDS_PER_DAY, STATS, SWEEP_DESTINATION_DELAY, VALIDATORS, VESTING_SCHEDULES,
 };
-use crate::state::{Config, CLAIM_INDEX, CONFIG, MERKLE_ROOT};
-use crate::submsg::{create_claim_response, create_fund_community_pool_response};
+use crate::submsg::{
+    create_claim_response, create_delegate_msg, create_fund_community_pool_msg, create_send_msgs,
+    CREATE_VESTING_ACCOUNT_REPLY_ID, DELEGATE_REPLY_ID,
+};
+use crate::sweep::{create_sweep_msgs, effective_sweep_destination, validate_sweep_destination};
 use crate::verification::verify_signature;
+use cw2::set_contract_version;
+use cw20::Cw20ReceiveMsg;
+use cw_storage_plus::Bound;
 
 use sha3::Digest;
 use std::convert::TryInto;
-use std::ops::SubAssign;
-use std::str::FromStr;
+
+// Pagination of list queries
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+// Addresses checked by a single AreClaimed query
+const MAX_ARE_CLAIMED: usize = 100;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -35,26 +69,121 @@ pub fn instantiate(
             "claim_end_time must be in the future",
         ));
     }
+    let claim_start_time = msg.claim_start_time.unwrap_or_default();
+    if claim_start_time >= msg.claim_end_time {
+        return Err(StdError::generic_err(
+            "claim_start_time must be before claim_end_time",
+        ));
+    }
+
+    let cliff_seconds = msg.cliff_seconds.unwrap_or_default();
+    validate_vesting_schedule(&msg.vesting_periods, cliff_seconds)?;
+    let clawback_funder = msg
+        .clawback_funder
+        .map(|funder| {
+            deps.api
+                .addr_validate(&funder)
+                .map(|funder| funder.to_string())
+        })
+        .transpose()?;
+
+    let token_type = msg.token_type.unwrap_or_default();
+    let mut denom = msg.denom;
+    let mut vesting_mode = msg.vesting_mode.clone().unwrap_or_default();
+    if token_type == TokenType::Cw20 {
+        denom = deps.api.addr_validate(&denom)?.to_string();
+        // Vesting accounts only hold native denoms
+        vesting_mode = match msg.vesting_mode {
+            None | Some(VestingMode::Escrow) => VestingMode::Escrow,
+            Some(VestingMode::Clawback) if clawback_funder.is_some() => VestingMode::Clawback,
+            Some(VestingMode::Clawback) => {
+                return Err(StdError::generic_err(
+                    "cw20 clawback requires a clawback funder",
+                ))
+            }
+            Some(_) => {
+                return Err(StdError::generic_err(
+                    "cw20 campaigns must vest through escrow",
+                ))
+            }
+        };
+    }
 
-    for periods in msg.vesting_periods {
-        if periods < 0 {
-            return Err(StdError::generic_err("periods must be greater than 0"));
+    validate_continuous_cliff(&vesting_mode, cliff_seconds)?;
+    // Same for a start time after the claim, which can't be set on the account
+    let now = env.block.time.seconds() as i64;
+    if vesting_mode == VestingMode::Continuous
+        && matches!(msg.start_time, Some(start_time) if start_time > now)
+    {
+        return Err(StdError::generic_err(
+            "continuous vesting cannot start in the future",
+        ));
+    }
+
+    let vesting_fallback = msg.vesting_fallback.unwrap_or_default();
+    let extra_denoms = msg.extra_denoms.unwrap_or_default();
+    for (i, extra_denom) in extra_denoms.iter().enumerate() {
+        if *extra_denom == denom || extra_denoms[..i].contains(extra_denom) {
+            return Err(StdError::generic_err(format!(
+                "duplicate denom: {}",
+                extra_denom
+            )));
         }
     }
+    // Escrowed tranches are tracked in a single denom
+    if !extra_denoms.is_empty()
+        && (vesting_mode == VestingMode::Escrow
+            || vesting_mode == VestingMode::Clawback
+            || vesting_fallback == VestingFallback::Escrow)
+    {
+        return Err(StdError::generic_err(
+            "escrow vesting supports a single denom",
+        ));
+    }
+
+    let admin = deps.api.addr_validate(&msg.admin)?.to_string();
+    let sweep_destination = match msg.sweep_destination {
+        Some(destination) => validate_sweep_destination(deps.api, &token_type, destination)?,
+        // The community pool only takes native denoms
+        None if token_type == TokenType::Cw20 => SweepDestination::Admin,
+        None => SweepDestination::CommunityPool,
+    };
+    let screening_contract = match msg.screening_contract {
+        Some(address) => Some(deps.api.addr_validate(&address)?.to_string()),
+        None => None,
+    };
+    if msg.gas_pool_denom.as_deref() == Some("") {
+        return Err(StdError::generic_err("gas_pool_denom cannot be empty"));
+    }
+    if let Some(claim_bonus) = &msg.claim_bonus {
+        validate_claim_bonus(claim_bonus)?;
+    }
 
     CONFIG.save(
         deps.storage,
         &Config {
-            admin: deps.api.addr_validate(&msg.admin)?.to_string(),
-            denom: msg.denom,
+            admin,
+            denom,
             prefix: msg.prefix,
             start_time: msg.start_time,
             vesting_periods: msg.vesting_periods,
             claim_end_time: msg.claim_end_time,
             fee_refund: msg.fee_refund,
             enabled: true,
+            vesting_mode,
+            vesting_fallback,
+            cliff_seconds,
+            clawback_funder,
+            extra_denoms,
+            token_type,
+            sweep_destination,
+            claim_start_time,
+            screening_contract,
+            gas_pool_denom: msg.gas_pool_denom,
+            claim_bonus: msg.claim_bonus,
         },
     )?;
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
     Ok(Response::default())
 }
@@ -63,69 +192,473 @@ pub fn instantiate(
 pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
     match msg {
         ExecuteMsg::UpdateConfig {
-            admin,
             fee_refund,
             enabled,
-        } => update_config(deps, env, info, admin, fee_refund, enabled),
-        ExecuteMsg::RegisterMerkleRoot { merkle_root } => {
-            register_merkle_root(deps, env, info, merkle_root)
+            sweep_destination,
+            claim_end_time,
+        } => update_config(
+            deps,
+            env,
+            info,
+            fee_refund,
+            enabled,
+            sweep_destination,
+            claim_end_time,
+        ),
+        ExecuteMsg::Pause { reason, until } => pause(deps, env, info, reason, until),
+        ExecuteMsg::Resume {} => resume(deps, env, info),
+        ExecuteMsg::ProposeAdmin { admin, expiry } => propose_admin(deps, env, info, admin, expiry),
+        ExecuteMsg::AcceptAdmin {} => accept_admin(deps, env, info),
+        ExecuteMsg::CancelAdminProposal {} => cancel_admin_proposal(deps, env, info),
+        ExecuteMsg::GrantRole { role, address } => grant_role(deps, env, info, role, address),
+        ExecuteMsg::RevokeRole { role, address } => revoke_role(deps, env, info, role, address),
+        ExecuteMsg::RegisterMerkleRoot {
+            merkle_root,
+            total_amount,
+        } => register_merkle_root(deps, env, info, merkle_root, total_amount),
+        ExecuteMsg::RegisterSchedule {
+            id,
+            periods,
+            cliff_seconds,
+        } => register_schedule(deps, env, info, id, periods, cliff_seconds),
+        ExecuteMsg::UpdateValidators { add, remove } => {
+            update_validators(deps, env, info, add, remove)
+        }
+        ExecuteMsg::UpdateBlocklist { add, remove } => {
+            update_blocklist(deps, env, info, add, remove)
+        }
+        ExecuteMsg::UpdateScreeningContract { address } => {
+            update_screening_contract(deps, env, info, address)
         }
         ExecuteMsg::Claim {
             allocation,
             proofs,
             message,
             signature,
-        } => claim(deps, env, info, allocation, proofs, message, signature),
+            delegate_to,
+        } => claim(
+            deps,
+            env,
+            info,
+            allocation,
+            proofs,
+            message,
+            signature,
+            delegate_to,
+        ),
         ExecuteMsg::End {} => end_airdrop(deps, env, info),
+        ExecuteMsg::Withdraw {} => withdraw(deps, env, info),
+        ExecuteMsg::Clawback { recipient } => clawback(deps, env, info, recipient),
+        ExecuteMsg::FundBonusPool {} => fund_bonus_pool(deps, env, info),
+        ExecuteMsg::FundGasPool {} => fund_gas_pool(deps, env, info),
+        ExecuteMsg::WithdrawGasPool { amount } => withdraw_gas_pool(deps, env, info, amount),
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
     }
 }
 
 pub fn update_config(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
-    admin: Option<String>,
     fee_refund: Option<Uint128>,
     enabled: Option<bool>,
+    sweep_destination: Option<SweepDestination>,
+    claim_end_time: Option<u64>,
 ) -> StdResult<Response> {
     let mut config: Config = CONFIG.load(deps.storage)?;
-    if info.sender != config.admin {
-        return Err(StdError::generic_err("unauthorized"));
+    if fee_refund.is_none()
+        && enabled.is_none()
+        && sweep_destination.is_none()
+        && claim_end_time.is_none()
+    {
+        if info.sender != config.admin {
+            return Err(StdError::generic_err("unauthorized"));
+        }
+        // Also what a legacy UpdateConfig { admin } turns into, since the
+        // unknown field is dropped when parsing
+        return Err(StdError::generic_err("no config field to update"));
     }
 
-    if let Some(admin) = admin {
-        config.admin = deps.api.addr_validate(&admin)?.to_string();
-    }
     if let Some(fee_refund) = fee_refund {
+        assert_role(deps.storage, &config, &info.sender, Role::Treasury)?;
         config.fee_refund = Some(fee_refund);
     }
     if let Some(enabled) = enabled {
+        // The pauser can only stop claims, resuming them is up to the admin
+        if enabled && info.sender != config.admin {
+            return Err(StdError::generic_err("unauthorized"));
+        }
+        assert_role(deps.storage, &config, &info.sender, Role::Pauser)?;
         config.enabled = enabled;
+        PAUSE.remove(deps.storage);
+    }
+    if let Some(destination) = sweep_destination {
+        assert_role(deps.storage, &config, &info.sender, Role::Treasury)?;
+        let destination = validate_sweep_destination(deps.api, &config.token_type, destination)?;
+
+        // Settle a change whose delay already passed before queuing the next one
+        let now = env.block.time.seconds();
+        config.sweep_destination = effective_sweep_destination(deps.storage, &config, now)?;
+        PENDING_SWEEP.save(
+            deps.storage,
+            &PendingSweep {
+                destination,
+                effective_time: now + SWEEP_DESTINATION_DELAY,
+            },
+        )?;
+    }
+    if let Some(claim_end_time) = claim_end_time {
+        if info.sender != config.admin {
+            return Err(StdError::generic_err("unauthorized"));
+        }
+        // The leftover may already be swept once the window closed
+        if env.block.time.seconds() > config.claim_end_time {
+            return Err(StdError::generic_err("airdrop event ended"));
+        }
+        if claim_end_time <= config.claim_end_time {
+            return Err(StdError::generic_err("claim_end_time can only be extended"));
+        }
+        config.claim_end_time = claim_end_time;
     }
 
     CONFIG.save(deps.storage, &config)?;
     Ok(Response::new().add_attribute("action", "update_config"))
 }
 
+pub fn pause(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    reason: String,
+    until: Option<u64>,
+) -> StdResult<Response> {
+    let mut config: Config = CONFIG.load(deps.storage)?;
+    assert_role(deps.storage, &config, &info.sender, Role::Pauser)?;
+
+    let now = env.block.time.seconds();
+    if reason.is_empty() {
+        return Err(StdError::generic_err("pause reason is required"));
+    }
+    if let Some(until) = until {
+        // Otherwise the pauser could swap an open-ended pause for one that
+        // ends right away, which is as good as resuming
+        if info.sender != config.admin {
+            return Err(StdError::generic_err("unauthorized"));
+        }
+        if until <= now {
+            return Err(StdError::generic_err("until must be in the future"));
+        }
+    }
+
+    config.enabled = false;
+    CONFIG.save(deps.storage, &config)?;
+    PAUSE.save(
+        deps.storage,
+        &PauseInfo {
+            reason: reason.clone(),
+            paused_at: now,
+            until,
+        },
+    )?;
+
+    let mut event = Event::new("airdrop_paused")
+        .add_attribute("paused_by", info.sender)
+        .add_attribute("reason", reason);
+    if let Some(until) = until {
+        event = event.add_attribute("until", until.to_string());
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "pause")
+        .add_event(event))
+}
+
+pub fn resume(deps: DepsMut, env: Env, info: MessageInfo) -> StdResult<Response> {
+    let mut config: Config = CONFIG.load(deps.storage)?;
+    // Like UpdateConfig, the pauser can only stop claims
+    if info.sender != config.admin {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+    if config.enabled {
+        return Err(StdError::generic_err("airdrop event is not paused"));
+    }
+
+    let pause = PAUSE.may_load(deps.storage)?;
+    config.enabled = true;
+    CONFIG.save(deps.storage, &config)?;
+    PAUSE.remove(deps.storage);
+
+    let mut event = Event::new("airdrop_resumed").add_attribute("resumed_by", info.sender);
+    if let Some(pause) = pause {
+        let paused_for = env.block.time.seconds().saturating_sub(pause.paused_at);
+        event = event
+            .add_attribute("reason", pause.reason)
+            .add_attribute("paused_seconds", paused_for.to_string());
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "resume")
+        .add_event(event))
+}
+
+pub fn propose_admin(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    admin: String,
+    expiry: Option<u64>,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    if let Some(expiry) = expiry {
+        if expiry <= env.block.time.seconds() {
+            return Err(StdError::generic_err("expiry must be in the future"));
+        }
+    }
+
+    // A new proposal replaces any pending one
+    let admin = deps.api.addr_validate(&admin)?.to_string();
+    PENDING_ADMIN.save(
+        deps.storage,
+        &PendingAdmin {
+            admin: admin.clone(),
+            expiry,
+        },
+    )?;
+
+    Ok(Response::new().add_attributes(vec![("action", "propose_admin"), ("admin", &admin)]))
+}
+
+pub fn accept_admin(deps: DepsMut, env: Env, info: MessageInfo) -> StdResult<Response> {
+    let pending = PENDING_ADMIN
+        .may_load(deps.storage)?
+        .ok_or_else(|| StdError::generic_err("no pending admin"))?;
+    if info.sender != pending.admin {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+    if let Some(expiry) = pending.expiry {
+        if env.block.time.seconds() >= expiry {
+            return Err(StdError::generic_err("admin proposal expired"));
+        }
+    }
+
+    let mut config: Config = CONFIG.load(deps.storage)?;
+    let previous = config.admin;
+    config.admin = pending.admin;
+    CONFIG.save(deps.storage, &config)?;
+    PENDING_ADMIN.remove(deps.storage);
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "accept_admin"),
+        ("previous_admin", &previous),
+        ("admin", &config.admin),
+    ]))
+}
+
+pub fn cancel_admin_proposal(deps: DepsMut, _env: Env, info: MessageInfo) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    if PENDING_ADMIN.may_load(deps.storage)?.is_none() {
+        return Err(StdError::generic_err("no pending admin"));
+    }
+    PENDING_ADMIN.remove(deps.storage);
+
+    Ok(Response::new().add_attribute("action", "cancel_admin_proposal"))
+}
+
+pub fn grant_role(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    role: Role,
+    address: String,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    let address = deps.api.addr_validate(&address)?.to_string();
+    ROLES.save(deps.storage, (role.as_str(), &address), &role)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "grant_role"),
+        ("role", role.as_str()),
+        ("address", &address),
+    ]))
+}
+
+pub fn revoke_role(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    role: Role,
+    address: String,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    if !ROLES.has(deps.storage, (role.as_str(), &address)) {
+        return Err(StdError::generic_err("role not granted"));
+    }
+    ROLES.remove(deps.storage, (role.as_str(), &address));
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "revoke_role"),
+        ("role", role.as_str()),
+        ("address", &address),
+    ]))
+}
+
 pub fn register_merkle_root(
     deps: DepsMut,
     _env: Env,
     info: MessageInfo,
     merkle_root: String,
+    total_amount: Option<Uint128>,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    assert_role(deps.storage, &config, &info.sender, Role::RootManager)?;
+
+    let mut root_buf: [u8; 32] = [0; 32];
+    hex::decode_to_slice(&merkle_root, &mut root_buf)
+        .map_err(|_| StdError::generic_err("invalid merkle root"))?;
+    MERKLE_ROOT.save(deps.storage, &root_buf)?;
+
+    let mut attributes = vec![
+        ("action", "register_merkle_root".to_string()),
+        ("merkle_root", merkle_root),
+    ];
+    if let Some(total_amount) = total_amount {
+        // Claims made under a previous root still count against the total
+        let mut accounting = ACCOUNTING.may_load(deps.storage)?.unwrap_or_default();
+        if total_amount < accounting.claimed + accounting.refunded {
+            return Err(StdError::generic_err(
+                "total_amount is below the amount already claimed",
+            ));
+        }
+        accounting.total_amount = Some(total_amount);
+        ACCOUNTING.save(deps.storage, &accounting)?;
+        attributes.push(("total_amount", total_amount.to_string()));
+    }
+
+    Ok(Response::new().add_attributes(attributes))
+}
+
+pub fn register_schedule(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    id: String,
+    periods: Vec<i64>,
+    cliff_seconds: Option<u64>,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    assert_role(deps.storage, &config, &info.sender, Role::RootManager)?;
+
+    // Leaves refer to the schedule by id, so it can't change once registered
+    if SCHEDULES.has(deps.storage, &id) {
+        return Err(StdError::generic_err("schedule already registered"));
+    }
+    if id.is_empty() || id.contains(',') {
+        return Err(StdError::generic_err("invalid schedule id"));
+    }
+
+    let cliff_seconds = cliff_seconds.unwrap_or_default();
+    validate_vesting_schedule(&periods, cliff_seconds)?;
+    validate_continuous_cliff(&config.vesting_mode, cliff_seconds)?;
+    SCHEDULES.save(
+        deps.storage,
+        &id,
+        &Schedule {
+            periods,
+            cliff_seconds,
+        },
+    )?;
+
+    Ok(Response::new().add_attributes(vec![("action", "register_schedule"), ("id", &id)]))
+}
+
+pub fn update_validators(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    add: Vec<String>,
+    remove: Vec<String>,
 ) -> StdResult<Response> {
     let config: Config = CONFIG.load(deps.storage)?;
     if info.sender != config.admin {
         return Err(StdError::generic_err("unauthorized"));
     }
 
-    MERKLE_ROOT.save(deps.storage, &merkle_root)?;
+    for validator in add.iter() {
+        VALIDATORS.save(deps.storage, validator, &true)?;
+    }
+    for validator in remove.iter() {
+        VALIDATORS.remove(deps.storage, validator);
+    }
+
+    Ok(Response::new().add_attribute("action", "update_validators"))
+}
+
+pub fn update_blocklist(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    add: Vec<String>,
+    remove: Vec<String>,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    // Signers and recipients are matched in their normalized form
+    for address in add.iter() {
+        BLOCKLIST.save(deps.storage, &normalize_address(address)?, &true)?;
+    }
+    for address in remove.iter() {
+        BLOCKLIST.remove(deps.storage, &normalize_address(address)?);
+    }
+
+    Ok(Response::new().add_attribute("action", "update_blocklist"))
+}
+
+pub fn update_screening_contract(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    address: Option<String>,
+) -> StdResult<Response> {
+    let mut config: Config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    config.screening_contract = match address {
+        Some(address) => Some(deps.api.addr_validate(&address)?.to_string()),
+        None => None,
+    };
+    CONFIG.save(deps.storage, &config)?;
 
     Ok(Response::new().add_attributes(vec![
-        ("action", "register_merkle_root"),
-        ("merkle_root", &merkle_root),
+        ("action", "update_screening_contract"),
+        (
+            "screening_contract",
+            config.screening_contract.as_deref().unwrap_or(""),
+        ),
     ]))
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn claim(
     deps: DepsMut,
     env: Env,
@@ -134,25 +667,214 @@ pub fn claim(
     proofs: Vec<String>,
     new_terra_address: String,
     signature: String,
+    delegate_to: Option<String>,
 ) -> StdResult<Response> {
     let config = CONFIG.load(deps.storage)?;
+    if let Some(validator) = &delegate_to {
+        // Only native denoms can be staked
+        if config.token_type != TokenType::Native {
+            return Err(StdError::generic_err(
+                "delegation requires a native campaign",
+            ));
+        }
+        if !VALIDATORS.has(deps.storage, validator) {
+            return Err(StdError::generic_err("validator not allowed"));
+        }
+    }
+    let outcome = prepare_claim(
+        deps.as_ref(),
+        &env,
+        &config,
+        info.sender.to_string(),
+        amount,
+        proofs,
+        new_terra_address,
+        signature,
+    )?;
 
-    // Make sure the airdrop is enabled
-    if !config.enabled {
-        return Err(StdError::generic_err("airdrop event is disabled"));
+    // Signed claims can be relayed by anyone, so the validator is left to
+    // the recipient, or to a terra signer sending their own claim
+    if delegate_to.is_some() && info.sender != outcome.recipient && info.sender != outcome.signer {
+        return Err(StdError::generic_err(
+            "delegate_to can only be set by the recipient",
+        ));
+    }
+
+    // Update claims so users' can't claim twice
+    CLAIM_INDEX.save(deps.storage, &outcome.signer, &true)?;
+    let vesting = sum_coins(
+        outcome
+            .vesting_periods
+            .iter()
+            .flat_map(|(_, coins)| coins.iter()),
+    );
+    // Sponsored refunds come out of the gas pool rather than the campaign
+    let (allocation_refund, sponsored_refund) = match &config.gas_pool_denom {
+        Some(_) => (Uint128::zero(), outcome.refund_amount),
+        None => (outcome.refund_amount, Uint128::zero()),
+    };
+    if !sponsored_refund.is_zero() {
+        GAS_POOL.update(deps.storage, |pool| -> StdResult<_> {
+            Ok(pool.checked_sub(sponsored_refund)?)
+        })?;
+    }
+    if !outcome.bonus.is_zero() {
+        BONUS_POOL.update(deps.storage, |pool| -> StdResult<_> {
+            Ok(pool.checked_sub(outcome.bonus)?)
+        })?;
+    }
+    record_claim(
+        deps.storage,
+        env.block.time.seconds(),
+        amount_of(&outcome.vested, &config.denom),
+        amount_of(&vesting, &config.denom),
+        outcome.bonus,
+        allocation_refund,
+        sponsored_refund,
+    )?;
+    CLAIM_RECORDS.save(
+        deps.storage,
+        &outcome.signer,
+        &ClaimRecord {
+            recipient: outcome.recipient.clone(),
+            liquid: outcome.vested.clone(),
+            vesting,
+            fee_refund: allocation_refund,
+            claimed_at: env.block.time.seconds(),
+            relayer: info.sender.to_string(),
+            gas_refund: match &config.gas_pool_denom {
+                Some(denom) if !sponsored_refund.is_zero() => Some(Coin {
+                    denom: denom.clone(),
+                    amount: sponsored_refund,
+                }),
+                _ => None,
+            },
+        },
+    )?;
+    RECIPIENT_CLAIMS.save(deps.storage, (&outcome.recipient, &outcome.signer), &true)?;
+
+    let start_time = config.start_time.unwrap_or(env.block.time.seconds() as i64);
+    if config.vesting_mode == VestingMode::Escrow || config.vesting_mode == VestingMode::Clawback {
+        // Escrowed tranches stay in the contract until the recipient withdraws them
+        let clawback = match config.vesting_mode {
+            VestingMode::Clawback => Some(ClawbackInfo {
+                funder: config.clawback_funder.clone(),
+            }),
+            _ => None,
+        };
+        record_schedule(
+            deps.storage,
+            &outcome.recipient,
+            start_time,
+            &outcome.vesting_periods,
+            clawback,
+        )?;
+    } else if !sum_coins(outcome.vesting_periods.iter().flat_map(|p| p.1.iter())).is_empty() {
+        // Keep the schedule around until the vesting account reply, in case
+        // the account can't be created
+        PENDING_VESTING.save(
+            deps.storage,
+            &PendingVesting {
+                recipient: outcome.recipient.clone(),
+                start_time,
+                periods: outcome.vesting_periods.clone(),
+            },
+        )?;
     }
 
-    // Make sure the airdrop claim period has not ended
+    let delegation = amount_of(&outcome.vested, &config.denom);
+    let response = create_claim_response(
+        env.clone(),
+        info.sender.to_string(),
+        config.token_type,
+        config.denom.clone(),
+        outcome.signer,
+        outcome.recipient.clone(),
+        outcome.vested,
+        outcome.vesting_periods,
+        config.start_time,
+        config.vesting_mode,
+        allocation_refund,
+    )?;
+    let response = match &config.gas_pool_denom {
+        Some(gas_pool_denom) if !sponsored_refund.is_zero() => response
+            .add_message(BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: coins(sponsored_refund.u128(), gas_pool_denom),
+            })
+            .add_attribute("gas_refund", sponsored_refund.to_string()),
+        _ => response,
+    };
+    let response = match &config.claim_bonus {
+        Some(_) => response.add_attributes(vec![
+            ("bonus_multiplier", outcome.multiplier.to_string()),
+            ("bonus", outcome.bonus.to_string()),
+        ]),
+        None => response,
+    };
+
+    match delegate_to {
+        Some(validator) if !delegation.is_zero() => {
+            let msg = create_delegate_msg(
+                env.contract.address.to_string(),
+                outcome.recipient,
+                validator.clone(),
+                Coin {
+                    denom: config.denom,
+                    amount: delegation,
+                },
+            );
+            // Without a grant the tokens simply stay liquid
+            Ok(response
+                .add_submessage(SubMsg::reply_on_error(msg, DELEGATE_REPLY_ID))
+                .add_attribute("delegate_to", validator))
+        }
+        _ => Ok(response),
+    }
+}
+
+// What a claim pays out once the leaf, proofs and signature check out
+struct ClaimOutcome {
+    signer: String,
+    recipient: String,
+    vested: Vec<Coin>,
+    vesting_periods: Vec<(i64, Vec<Coin>)>,
+    refund_amount: Uint128,
+    multiplier: Decimal,
+    bonus: Uint128,
+}
+
+// Runs every check of a claim without writing state, shared by claim and
+// the SimulateClaim query
+#[allow(clippy::too_many_arguments)]
+fn prepare_claim(
+    deps: Deps,
+    env: &Env,
+    config: &Config,
+    sender: String,
+    amount: String,
+    proofs: Vec<String>,
+    new_terra_address: String,
+    signature: String,
+) -> StdResult<ClaimOutcome> {
+    // Make sure the airdrop is enabled
+    assert_not_paused(deps.storage, config, env.block.time.seconds())?;
+
+    // Make sure the airdrop claim period has started and not ended
+    if env.block.time.seconds() < config.claim_start_time {
+        return Err(StdError::generic_err("airdrop event not started"));
+    }
     if env.block.time.seconds() > config.claim_end_time {
         return Err(StdError::generic_err("airdrop event ended"));
     }
 
     // Parse claim string to get signer address
-    let mut values = (&amount).split(",");
-    let signer = values
-        .next()
-        .ok_or(StdError::generic_err("unable to parse claim amount"))?
-        .to_lowercase();
+    let mut values = amount.split(',');
+    let signer = normalize_address(
+        values
+            .next()
+            .ok_or(StdError::generic_err("unable to parse claim amount"))?,
+    )?;
 
     // Check if user has already claimed
     if CLAIM_INDEX
@@ -163,65 +885,115 @@ pub fn claim(
     }
 
     // Verify signature
-    let verified_terra_address = verify_signature(
-        deps.as_ref(),
-        String::from(&info.sender),
+    let verified_terra_address = normalize_address(&verify_signature(
+        deps,
+        sender,
         new_terra_address,
         signature,
         signer.clone(),
-    )?;
+    )?)?;
 
     // Parse vested component from claim string
     let amount0 = values
         .next()
         .ok_or(StdError::generic_err("unable to parse claim amount0"))?;
-    let mut amount0_u128 = u128::from_str_radix(amount0, 10)
-        .map_err(|_| StdError::generic_err("unable to parse amount0"))?;
+    let mut vested = parse_coins(config, amount0).map_err(|err| match err {
+        StdError::GenericErr { .. } => err,
+        _ => StdError::generic_err("unable to parse amount0"),
+    })?;
+
+    let mut vesting_periods = parse_vesting_tranches(deps.storage, config, values)?;
 
-    // Deduct fees from vested component if its more than
     let mut refund_amount: Uint128 = Uint128::new(0);
     if let Some(fee_refund) = config.fee_refund {
-        let fee_u128 = fee_refund.u128();
-        if amount0_u128.ge(&fee_u128) {
-            amount0_u128.sub_assign(fee_u128);
-            refund_amount = fee_refund;
-        }
-    }
+        if config.gas_pool_denom.is_some() {
+            // Sponsored refunds leave the allocation untouched and are
+            // skipped once the pool runs dry
+            if GAS_POOL.may_load(deps.storage)?.unwrap_or_default() >= fee_refund {
+                refund_amount = fee_refund;
+            }
+        } else {
+            // Deduct fees from vested component if its more than
+            if deduct_fee(&mut vested, &config.denom, fee_refund) {
+                refund_amount = fee_refund;
+            }
 
-    // Parse and deduct fees from vested component
-    // Note: If none of the amounts can be used to pay for gas, no fees will be deducted
-    // In practice, we have a dust filter of 1 LUNA so all airdrops should have enough fees
-    let mut vesting_periods: Vec<(i64, String)> = vec![];
-    for i in 0..5 {
-        let mut amount_string = values
-            .next()
-            .ok_or(StdError::generic_err(format!(
-                "unable to parse claim amount {}",
-                i + 1
-            )))?
-            .to_string();
-        if let Some(fee_refund) = config.fee_refund {
-            if refund_amount.is_zero() {
-                let amount = Uint128::from_str(&amount_string)?;
-                if amount.ge(&fee_refund) {
-                    amount_string = amount.checked_sub(fee_refund)?.to_string();
+            // Otherwise deduct fees from the first tranche that covers them
+            // Note: If none of the amounts can be used to pay for gas, no fees will be deducted
+            // In practice, we have a dust filter of 1 LUNA so all airdrops should have enough fees
+            for (_, coins) in vesting_periods.iter_mut() {
+                if refund_amount.is_zero() && deduct_fee(coins, &config.denom, fee_refund) {
                     refund_amount = fee_refund;
                 }
             }
         }
-        vesting_periods.push((config.vesting_periods[i], amount_string));
     }
 
     // Verify if claim amount is part of merkle tree
-    let merkle_root: String = MERKLE_ROOT.load(deps.storage)?;
-    let mut hash: [u8; 32] = sha3::Keccak256::digest(amount.as_bytes())
+    let merkle_root = MERKLE_ROOT.load(deps.storage)?;
+    verify_merkle_proof(merkle_root, &amount, proofs)?;
+
+    let mut payout = vested.clone();
+    payout.extend(
+        vesting_periods
+            .iter()
+            .flat_map(|(_, coins)| coins.iter().cloned()),
+    );
+    if !refund_amount.is_zero() && config.gas_pool_denom.is_none() {
+        payout.push(Coin {
+            denom: config.denom.clone(),
+            amount: refund_amount,
+        });
+    }
+    assert_solvent(deps, env, config, &sum_coins(payout.iter()))?;
+
+    // The bonus is paid by its own pool, on top of the solvent allocation
+    let (multiplier, bonus) = apply_bonus(
+        deps.storage,
+        config,
+        env.block.time.seconds(),
+        &mut vested,
+        &mut vesting_periods,
+    )?;
+
+    assert_not_blocked(deps, config, &[&signer, &verified_terra_address])?;
+
+    Ok(ClaimOutcome {
+        signer,
+        recipient: verified_terra_address,
+        vested,
+        vesting_periods,
+        refund_amount,
+        multiplier,
+        bonus,
+    })
+}
+
+// Deducts the fee from the `denom` coin if it covers it, dropping the coin
+// once nothing is left
+fn deduct_fee(coins: &mut Vec<Coin>, denom: &str, fee: Uint128) -> bool {
+    let deducted = match coins.iter_mut().find(|c| c.denom == denom) {
+        Some(coin) if coin.amount >= fee => {
+            coin.amount -= fee;
+            true
+        }
+        _ => false,
+    };
+    coins.retain(|c| !c.amount.is_zero());
+
+    deducted
+}
+
+fn verify_merkle_proof(merkle_root: [u8; 32], leaf: &str, proofs: Vec<String>) -> StdResult<()> {
+    let mut hash: [u8; 32] = sha3::Keccak256::digest(leaf.as_bytes())
         .as_slice()
         .try_into()
         .expect("Wrong length");
 
     for p in proofs {
         let mut proof_buf: [u8; 32] = [0; 32];
-        hex::decode_to_slice(p, &mut proof_buf).unwrap();
+        hex::decode_to_slice(p, &mut proof_buf)
+            .map_err(|_| StdError::generic_err("invalid proof"))?;
         hash = if bytes_cmp(hash, proof_buf) == std::cmp::Ordering::Less {
             sha3::Keccak256::digest(&[hash, proof_buf].concat())
                 .as_slice()
@@ -235,47 +1007,279 @@ pub fn claim(
         };
     }
 
-    let mut root_buf: [u8; 32] = [0; 32];
-    hex::decode_to_slice(merkle_root, &mut root_buf).unwrap();
-    if root_buf != hash {
+    if merkle_root != hash {
         return Err(StdError::generic_err("Merkle verification failed"));
     }
 
-    // Update claims so users' can't claim twice
-    CLAIM_INDEX.save(deps.storage, &signer, &true)?;
-
-    Ok(create_claim_response(
-        env,
-        info.sender.to_string(),
-        config.denom,
-        signer.to_string(),
-        verified_terra_address,
-        amount0_u128,
-        vesting_periods,
-        config.start_time,
-        refund_amount,
-    )?)
+    Ok(())
 }
 
 fn end_airdrop(deps: DepsMut, env: Env, info: MessageInfo) -> StdResult<Response> {
     let config: Config = CONFIG.load(deps.storage)?;
-    if info.sender != config.admin {
-        return Err(StdError::generic_err("unauthorized"));
-    }
+    assert_role(deps.storage, &config, &info.sender, Role::Treasury)?;
 
     if env.block.time.seconds() < config.claim_end_time {
         return Err(StdError::generic_err("airdrop event not ended"));
     }
 
-    let coin = deps
-        .querier
-        .query_balance(env.contract.address.clone(), config.denom.clone())?;
+    // Funds held in escrow still belong to recipients, and the gas pool is
+    // withdrawn separately, whichever campaign denom it shares. The bonus
+    // pool goes with the leftover
+    BONUS_POOL.remove(deps.storage);
 
-    create_fund_community_pool_response(
-        config.denom,
-        env.contract.address.into_string(),
-        coin.amount,
-    )
+    let mut amount: Vec<Coin> = vec![];
+    if config.token_type == TokenType::Cw20 {
+        // CW20 transfers reject zero amounts
+        let balance = query_balance(deps.as_ref(), &env, &config, &config.denom)?
+            .saturating_sub(reserved_balance(deps.storage, &config, &config.denom)?);
+        if !balance.is_zero() {
+            amount.push(Coin {
+                denom: config.denom.clone(),
+                amount: balance,
+            });
+        }
+    } else {
+        for denom in config.denoms() {
+            let mut coin = deps
+                .querier
+                .query_balance(env.contract.address.clone(), denom.clone())?;
+            coin.amount =
+                coin.amount
+                    .saturating_sub(reserved_balance(deps.storage, &config, denom)?);
+            // Bank and distribution messages reject zero amounts
+            if !coin.amount.is_zero() {
+                amount.push(coin);
+            }
+        }
+        amount.sort_by(|a, b| a.denom.cmp(&b.denom));
+    }
+
+    let destination = effective_sweep_destination(deps.storage, &config, env.block.time.seconds())?
+        .resolve(&config.admin);
+    let msgs = if amount.is_empty() {
+        vec![]
+    } else {
+        create_sweep_msgs(
+            &config.token_type,
+            &destination,
+            env.contract.address.to_string(),
+            amount,
+        )?
+    };
+
+    let mut attributes = vec![
+        ("action", "end_airdrop".to_string()),
+        ("destination", destination.route().to_string()),
+    ];
+    if let Some(address) = destination.address() {
+        attributes.push(("recipient", address.to_string()));
+    }
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_attributes(attributes))
+}
+
+fn withdraw(deps: DepsMut, env: Env, info: MessageInfo) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let recipient = info.sender.to_string();
+
+    let mut schedule = VESTING_SCHEDULES
+        .may_load(deps.storage, &recipient)?
+        .ok_or_else(|| StdError::generic_err("no vesting schedule"))?;
+
+    let amount = schedule.withdrawable(env.block.time.seconds());
+    if amount.is_zero() {
+        return Err(StdError::generic_err("nothing to withdraw"));
+    }
+
+    schedule.withdrawn += amount;
+    VESTING_SCHEDULES.save(deps.storage, &recipient, &schedule)?;
+    ESCROWED.update(deps.storage, |escrowed| -> StdResult<_> {
+        Ok(escrowed.checked_sub(amount)?)
+    })?;
+
+    Ok(Response::new()
+        .add_messages(create_send_msgs(
+            &config.token_type,
+            recipient.clone(),
+            coins(amount.u128(), config.denom),
+        )?)
+        .add_attributes(vec![
+            ("action", "withdraw"),
+            ("recipient", &recipient),
+            ("amount", &amount.to_string()),
+        ]))
+}
+
+fn clawback(deps: DepsMut, env: Env, info: MessageInfo, recipient: String) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    assert_role(deps.storage, &config, &info.sender, Role::Treasury)?;
+
+    // Schedules are keyed by the recipient as claim normalized it
+    let recipient = normalize_address(&recipient)?;
+    let mut schedule = VESTING_SCHEDULES
+        .may_load(deps.storage, &recipient)?
+        .ok_or_else(|| StdError::generic_err("no vesting schedule"))?;
+    let funder = match &schedule.clawback {
+        Some(clawback) => clawback.funder.clone(),
+        None => return Err(StdError::generic_err("vesting schedule is not revocable")),
+    };
+
+    // Whatever already unlocked stays withdrawable by the recipient
+    let amount = schedule.revoke_locked(env.block.time.seconds());
+    if amount.is_zero() {
+        return Err(StdError::generic_err("nothing to claw back"));
+    }
+
+    VESTING_SCHEDULES.save(deps.storage, &recipient, &schedule)?;
+    ESCROWED.update(deps.storage, |escrowed| -> StdResult<_> {
+        Ok(escrowed.checked_sub(amount)?)
+    })?;
+
+    let (msgs, destination) = match funder {
+        Some(funder) => (
+            create_send_msgs(
+                &config.token_type,
+                funder.clone(),
+                coins(amount.u128(), config.denom),
+            )?,
+            funder,
+        ),
+        None => (
+            vec![create_fund_community_pool_msg(
+                env.contract.address.to_string(),
+                coins(amount.u128(), config.denom),
+            )],
+            "community_pool".to_string(),
+        ),
+    };
+
+    Ok(Response::new().add_messages(msgs).add_attributes(vec![
+        ("action", "clawback"),
+        ("recipient", &recipient),
+        ("amount", &amount.to_string()),
+        ("destination", &destination),
+    ]))
+}
+
+fn fund_bonus_pool(deps: DepsMut, _env: Env, info: MessageInfo) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    if config.token_type == TokenType::Cw20 {
+        return Err(StdError::generic_err(
+            "cw20 campaigns fund the bonus pool through the FundBonusPool hook",
+        ));
+    }
+
+    let amount = match info.funds.as_slice() {
+        [coin] if coin.denom == config.denom && !coin.amount.is_zero() => coin.amount,
+        _ => {
+            return Err(StdError::generic_err(format!(
+                "bonus pool only accepts {}",
+                config.denom
+            )))
+        }
+    };
+    add_to_bonus_pool(deps, info.sender.as_str(), amount)
+}
+
+fn add_to_bonus_pool(deps: DepsMut, sender: &str, amount: Uint128) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    if config.claim_bonus.is_none() {
+        return Err(StdError::generic_err("claim bonus is not enabled"));
+    }
+
+    let pool = BONUS_POOL.may_load(deps.storage)?.unwrap_or_default() + amount;
+    BONUS_POOL.save(deps.storage, &pool)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "fund_bonus_pool"),
+        ("sender", sender),
+        ("amount", &amount.to_string()),
+        ("bonus_pool", &pool.to_string()),
+    ]))
+}
+
+fn fund_gas_pool(deps: DepsMut, _env: Env, info: MessageInfo) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let denom = config
+        .gas_pool_denom
+        .ok_or_else(|| StdError::generic_err("gas pool is not enabled"))?;
+
+    let amount = match info.funds.as_slice() {
+        [coin] if coin.denom == denom && !coin.amount.is_zero() => coin.amount,
+        _ => {
+            return Err(StdError::generic_err(format!(
+                "gas pool only accepts {}",
+                denom
+            )))
+        }
+    };
+    let pool = GAS_POOL.may_load(deps.storage)?.unwrap_or_default() + amount;
+    GAS_POOL.save(deps.storage, &pool)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "fund_gas_pool"),
+        ("sender", info.sender.as_str()),
+        ("amount", &amount.to_string()),
+        ("gas_pool", &pool.to_string()),
+    ]))
+}
+
+fn withdraw_gas_pool(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    amount: Option<Uint128>,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    assert_role(deps.storage, &config, &info.sender, Role::Treasury)?;
+    let denom = config
+        .gas_pool_denom
+        .ok_or_else(|| StdError::generic_err("gas pool is not enabled"))?;
+
+    let pool = GAS_POOL.may_load(deps.storage)?.unwrap_or_default();
+    let amount = amount.unwrap_or(pool);
+    if amount.is_zero() {
+        return Err(StdError::generic_err("nothing to withdraw"));
+    }
+    if amount > pool {
+        return Err(StdError::generic_err("amount exceeds the gas pool"));
+    }
+    GAS_POOL.save(deps.storage, &(pool - amount))?;
+
+    Ok(Response::new()
+        .add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: coins(amount.u128(), denom),
+        })
+        .add_attributes(vec![
+            ("action", "withdraw_gas_pool"),
+            ("recipient", info.sender.as_str()),
+            ("amount", &amount.to_string()),
+        ]))
+}
+
+fn receive_cw20(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: Cw20ReceiveMsg,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    // Only the campaign token can fund the contract
+    if config.token_type != TokenType::Cw20 || info.sender != config.denom {
+        return Err(StdError::generic_err("unauthorized token"));
+    }
+
+    match from_binary(&msg.msg)? {
+        Cw20HookMsg::Fund {} => Ok(Response::new().add_attributes(vec![
+            ("action", "fund"),
+            ("sender", &msg.sender),
+            ("amount", &msg.amount.to_string()),
+        ])),
+        Cw20HookMsg::FundBonusPool {} => add_to_bonus_pool(deps, &msg.sender, msg.amount),
+    }
 }
 
 fn bytes_cmp(a: [u8; 32], b: [u8; 32]) -> std::cmp::Ordering {
@@ -293,46 +1297,519 @@ fn bytes_cmp(a: [u8; 32], b: [u8; 32]) -> std::cmp::Ordering {
     std::cmp::Ordering::Equal
 }
 
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> StdResult<Response> {
+    match msg.id {
+        CREATE_VESTING_ACCOUNT_REPLY_ID => vesting_account_fallback(deps, env, msg.result),
+        DELEGATE_REPLY_ID => delegation_fallback(msg.result),
+        id => Err(StdError::generic_err(format!("unknown reply id: {}", id))),
+    }
+}
+
+fn delegation_fallback(result: SubMsgResult) -> StdResult<Response> {
+    match result {
+        SubMsgResult::Err(reason) => Ok(Response::new()
+            .add_event(Event::new("delegation_failed").add_attribute("reason", reason))),
+        SubMsgResult::Ok(_) => Ok(Response::default()),
+    }
+}
+
+fn vesting_account_fallback(deps: DepsMut, _env: Env, result: SubMsgResult) -> StdResult<Response> {
+    // Successes are sent back too, so the schedule never outlives its claim
+    let pending = PENDING_VESTING.load(deps.storage)?;
+    PENDING_VESTING.remove(deps.storage);
+    let reason = match result {
+        SubMsgResult::Err(err) => err,
+        SubMsgResult::Ok(_) => return Ok(Response::default()),
+    };
+
+    let config: Config = CONFIG.load(deps.storage)?;
+    let (response, policy) = match config.vesting_fallback {
+        VestingFallback::Escrow => {
+            // Escrow releases whole tranches. A delayed account unlocks
+            // everything at the end of the last period and a continuous one
+            // unlocks linearly until then, so neither may release a tranche
+            // early and both are held until the end
+            let periods = match config.vesting_mode {
+                VestingMode::Continuous | VestingMode::Delayed => vec![(
+                    pending.periods.iter().map(|p| p.0).sum(),
+                    sum_coins(pending.periods.iter().flat_map(|p| p.1.iter())),
+                )],
+                _ => pending.periods,
+            };
+            record_schedule(
+                deps.storage,
+                &pending.recipient,
+                pending.start_time,
+                &periods,
+                None,
+            )?;
+            (Response::new(), "escrow")
+        }
+        VestingFallback::Liquid => {
+            let total = sum_coins(pending.periods.iter().flat_map(|p| p.1.iter()));
+            let response = Response::new().add_message(BankMsg::Send {
+                to_address: pending.recipient.clone(),
+                amount: total,
+            });
+            (response, "liquid")
+        }
+        VestingFallback::Revert => {
+            return Err(StdError::generic_err(format!(
+                "unable to create vesting account: {}",
+                reason
+            )));
+        }
+    };
+
+    Ok(response.add_event(
+        Event::new("vesting_fallback")
+            .add_attribute("recipient", pending.recipient)
+            .add_attribute("policy", policy)
+            .add_attribute("reason", reason),
+    ))
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Config {} => to_binary(&query_config(deps, env)?),
+        QueryMsg::PendingAdmin {} => to_binary(&query_pending_admin(deps, env)?),
+        QueryMsg::Roles {} => to_binary(&query_roles(deps, env)?),
         QueryMsg::MerkleRoot {} => to_binary(&query_merkle_root(deps, env)?),
         QueryMsg::IsClaimed { address } => to_binary(&query_is_claimed(deps, env, address)?),
+        QueryMsg::AreClaimed { addresses } => to_binary(&query_are_claimed(deps, env, addresses)?),
+        QueryMsg::VestingStatus { address } => {
+            to_binary(&query_vesting_status(deps, env, address)?)
+        }
+        QueryMsg::Schedule { id } => to_binary(&query_schedule(deps, env, id)?),
+        QueryMsg::Validators {} => to_binary(&query_validators(deps, env)?),
+        QueryMsg::Blocklist {} => to_binary(&query_blocklist(deps, env)?),
+        QueryMsg::Solvency {} => to_binary(&query_solvency(deps, env)?),
+        QueryMsg::GasPool {} => to_binary(&query_gas_pool(deps, env)?),
+        QueryMsg::ClaimBonus {} => to_binary(&query_claim_bonus(deps, env)?),
+        QueryMsg::Stats { start_after, limit } => {
+            to_binary(&query_stats(deps, env, start_after, limit)?)
+        }
+        QueryMsg::ListClaims { start_after, limit } => {
+            to_binary(&query_list_claims(deps, env, start_after, limit)?)
+        }
+        QueryMsg::ClaimsByRecipient {
+            recipient,
+            start_after,
+            limit,
+        } => to_binary(&query_claims_by_recipient(
+            deps,
+            env,
+            recipient,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::SimulateClaim {
+            allocation,
+            proofs,
+            message,
+            signature,
+            sender,
+        } => to_binary(&query_simulate_claim(
+            deps, env, allocation, proofs, message, signature, sender,
+        )?),
     }
 }
 
-pub fn query_config(deps: Deps, _env: Env) -> StdResult<ConfigResponse> {
+pub fn query_config(deps: Deps, env: Env) -> StdResult<ConfigResponse> {
     let state = CONFIG.load(deps.storage)?;
+    let now = env.block.time.seconds();
+    let sweep_destination = effective_sweep_destination(deps.storage, &state, now)?;
+    let pending_sweep_destination = PENDING_SWEEP
+        .may_load(deps.storage)?
+        .filter(|pending| pending.effective_time > now);
+    let pause = pause_state(deps.storage, &state, now)?;
     let resp = ConfigResponse {
         admin: state.admin,
         denom: state.denom,
         fee: state.fee_refund,
-        enabled: state.enabled,
+        enabled: matches!(pause, PauseState::Live),
+        vesting_mode: state.vesting_mode,
+        cliff_seconds: state.cliff_seconds,
+        extra_denoms: state.extra_denoms,
+        token_type: state.token_type,
+        sweep_destination,
+        pending_sweep_destination,
+        claim_start_time: state.claim_start_time,
+        claim_end_time: state.claim_end_time,
+        screening_contract: state.screening_contract,
+        gas_pool_denom: state.gas_pool_denom,
+        claim_bonus: state.claim_bonus,
+        pause: match pause {
+            PauseState::Paused(pause) => Some(pause),
+            _ => None,
+        },
     };
 
     Ok(resp)
 }
 
+pub fn query_pending_admin(deps: Deps, _env: Env) -> StdResult<PendingAdminResponse> {
+    let pending = PENDING_ADMIN.may_load(deps.storage)?;
+    let resp = PendingAdminResponse {
+        admin: pending.as_ref().map(|p| p.admin.clone()),
+        expiry: pending.and_then(|p| p.expiry),
+    };
+
+    Ok(resp)
+}
+
+pub fn query_roles(deps: Deps, _env: Env) -> StdResult<RolesResponse> {
+    let roles = ROLES
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| {
+            let ((_, address), role) = item?;
+            Ok(RoleResponse { role, address })
+        })
+        .collect::<StdResult<Vec<RoleResponse>>>()?;
+    let resp = RolesResponse { roles };
+
+    Ok(resp)
+}
+
 pub fn query_merkle_root(deps: Deps, _env: Env) -> StdResult<MerkleRootResponse> {
     let merkle_root = MERKLE_ROOT.load(deps.storage)?;
-    let resp = MerkleRootResponse { merkle_root };
+    let resp = MerkleRootResponse {
+        merkle_root: hex::encode(merkle_root),
+    };
 
     Ok(resp)
 }
 
+// Looks the signer up the same way claim records it. An address that
+// doesn't normalize can't have claimed
+fn is_claimed(deps: Deps, address: &str) -> StdResult<bool> {
+    match normalize_address(address) {
+        Ok(address) => Ok(CLAIM_INDEX
+            .may_load(deps.storage, &address)?
+            .unwrap_or(false)),
+        Err(_) => Ok(false),
+    }
+}
+
 pub fn query_is_claimed(deps: Deps, _env: Env, address: String) -> StdResult<IsClaimedResponse> {
     let resp = IsClaimedResponse {
-        is_claimed: CLAIM_INDEX
-            .may_load(deps.storage, &address.to_lowercase())?
-            .unwrap_or(false),
+        is_claimed: is_claimed(deps, &address)?,
     };
 
     Ok(resp)
 }
 
+pub fn query_are_claimed(
+    deps: Deps,
+    _env: Env,
+    addresses: Vec<String>,
+) -> StdResult<AreClaimedResponse> {
+    if addresses.len() > MAX_ARE_CLAIMED {
+        return Err(StdError::generic_err(format!(
+            "too many addresses, at most {} per query",
+            MAX_ARE_CLAIMED
+        )));
+    }
+
+    let claims = addresses
+        .into_iter()
+        .map(|address| {
+            Ok(ClaimStatusResponse {
+                is_claimed: is_claimed(deps, &address)?,
+                address,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    let resp = AreClaimedResponse { claims };
+
+    Ok(resp)
+}
+
+pub fn query_vesting_status(
+    deps: Deps,
+    env: Env,
+    address: String,
+) -> StdResult<VestingStatusResponse> {
+    let schedule = VESTING_SCHEDULES
+        .may_load(deps.storage, &normalize_address(&address)?)?
+        .unwrap_or_default();
+    let unlocked = schedule.unlocked(env.block.time.seconds());
+    let resp = VestingStatusResponse {
+        locked: schedule.total() - unlocked,
+        unlocked,
+        withdrawn: schedule.withdrawn,
+    };
+
+    Ok(resp)
+}
+
+pub fn query_validators(deps: Deps, _env: Env) -> StdResult<ValidatorsResponse> {
+    let validators = VALIDATORS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<String>>>()?;
+    let resp = ValidatorsResponse { validators };
+
+    Ok(resp)
+}
+
+fn to_claim_response(signer: String, record: ClaimRecord) -> ClaimRecordResponse {
+    ClaimRecordResponse {
+        signer,
+        recipient: record.recipient,
+        liquid: record.liquid,
+        vesting: record.vesting,
+        fee_refund: record.fee_refund,
+        claimed_at: record.claimed_at,
+        relayer: record.relayer,
+        gas_refund: record.gas_refund,
+    }
+}
+
+pub fn query_list_claims(
+    deps: Deps,
+    _env: Env,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<ClaimsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after
+        .map(|signer| normalize_address(&signer))
+        .transpose()?;
+    let start = start_after.as_deref().map(Bound::exclusive);
+    let claims = CLAIM_RECORDS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (signer, record) = item?;
+            Ok(to_claim_response(signer, record))
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    let resp = ClaimsResponse { claims };
+
+    Ok(resp)
+}
+
+pub fn query_claims_by_recipient(
+    deps: Deps,
+    _env: Env,
+    recipient: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<ClaimsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let recipient = normalize_address(&recipient)?;
+    let start_after = start_after
+        .map(|signer| normalize_address(&signer))
+        .transpose()?;
+    let start = start_after.as_deref().map(Bound::exclusive);
+    let claims = RECIPIENT_CLAIMS
+        .prefix(&recipient)
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|signer| {
+            let signer = signer?;
+            let record = CLAIM_RECORDS.load(deps.storage, &signer)?;
+            Ok(to_claim_response(signer, record))
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    let resp = ClaimsResponse { claims };
+
+    Ok(resp)
+}
+
+pub fn query_stats(
+    deps: Deps,
+    _env: Env,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<StatsResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let stats = STATS.may_load(deps.storage)?.unwrap_or_default();
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|time| Bound::exclusive(time / SECONDS_PER_DAY));
+    let daily = DAILY_CLAIMS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (day, claims) = item?;
+            Ok(DailyClaimsResponse {
+                day: day * SECONDS_PER_DAY,
+                claims,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    let resp = StatsResponse {
+        claims: stats.claims,
+        liquid: stats.liquid,
+        vesting: stats.vesting,
+        fee_refunds: stats.fee_refunds,
+        bonus: stats.bonus,
+        gas_refunds: config.gas_pool_denom.map(|denom| Coin {
+            denom,
+            amount: stats.gas_refunds,
+        }),
+        daily,
+    };
+
+    Ok(resp)
+}
+
+pub fn query_solvency(deps: Deps, env: Env) -> StdResult<SolvencyResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let accounting = ACCOUNTING.may_load(deps.storage)?.unwrap_or_default();
+    let escrowed = ESCROWED.may_load(deps.storage)?.unwrap_or_default();
+    let balance = query_balance(deps, &env, &config, &config.denom)?;
+    let bonus_pool = BONUS_POOL.may_load(deps.storage)?.unwrap_or_default();
+    let gas_pool = reserved_balance(deps.storage, &config, &config.denom)? - escrowed - bonus_pool;
+
+    let unclaimed = accounting
+        .total_amount
+        .unwrap_or_default()
+        .saturating_sub(accounting.claimed + accounting.refunded);
+    let liability = unclaimed + escrowed + gas_pool + bonus_pool;
+    let resp = SolvencyResponse {
+        denom: config.denom,
+        balance,
+        total_amount: accounting.total_amount,
+        claimed: accounting.claimed,
+        refunded: accounting.refunded,
+        escrowed,
+        gas_pool,
+        bonus_pool,
+        liability,
+        surplus: balance.saturating_sub(liability),
+        shortfall: liability.saturating_sub(balance),
+    };
+
+    Ok(resp)
+}
+
+pub fn query_gas_pool(deps: Deps, _env: Env) -> StdResult<GasPoolResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let resp = GasPoolResponse {
+        denom: config.gas_pool_denom,
+        balance: GAS_POOL.may_load(deps.storage)?.unwrap_or_default(),
+    };
+
+    Ok(resp)
+}
+
+pub fn query_claim_bonus(deps: Deps, env: Env) -> StdResult<ClaimBonusResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let resp = ClaimBonusResponse {
+        multiplier: bonus_multiplier(&config, env.block.time.seconds()),
+        claim_bonus: config.claim_bonus,
+        pool: BONUS_POOL.may_load(deps.storage)?.unwrap_or_default(),
+    };
+
+    Ok(resp)
+}
+
+pub fn query_blocklist(deps: Deps, _env: Env) -> StdResult<BlocklistResponse> {
+    let addresses = BLOCKLIST
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<String>>>()?;
+    let resp = BlocklistResponse { addresses };
+
+    Ok(resp)
+}
+
+pub fn query_schedule(deps: Deps, _env: Env, id: String) -> StdResult<ScheduleResponse> {
+    let schedule = SCHEDULES.load(deps.storage, &id)?;
+    let resp = ScheduleResponse {
+        id,
+        periods: schedule.periods,
+        cliff_seconds: schedule.cliff_seconds,
+    };
+
+    Ok(resp)
+}
+
+pub fn query_simulate_claim(
+    deps: Deps,
+    env: Env,
+    allocation: String,
+    proofs: Vec<String>,
+    message: String,
+    signature: String,
+    sender: String,
+) -> StdResult<SimulateClaimResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let outcome = prepare_claim(
+        deps, &env, &config, sender, allocation, proofs, message, signature,
+    )
+    .map(|outcome| {
+        let start_time = config.start_time.unwrap_or(env.block.time.seconds() as i64);
+        let vesting = simulate_unlocks(&config.vesting_mode, start_time, &outcome.vesting_periods);
+        (outcome, vesting)
+    });
+
+    let resp = match outcome {
+        Ok((outcome, vesting)) => SimulateClaimResponse {
+            recipient: Some(outcome.recipient),
+            liquid: outcome.vested,
+            vesting,
+            fee_refund: outcome.refund_amount,
+            bonus: outcome.bonus,
+            error: None,
+        },
+        Err(err) => SimulateClaimResponse {
+            recipient: None,
+            liquid: vec![],
+            vesting: vec![],
+            fee_refund: Uint128::zero(),
+            bonus: Uint128::zero(),
+            error: Some(match err {
+                StdError::GenericErr { msg, .. } => msg,
+                err => err.to_string(),
+            }),
+        },
+    };
+
+    Ok(resp)
+}
+
+// Absolute unlock time of each tranche. Continuous and delayed accounts
+// unlock everything by the end of the last period, so they report a single
+// entry at that time
+fn simulate_unlocks(
+    vesting_mode: &VestingMode,
+    start_time: i64,
+    periods: &[(i64, Vec<Coin>)],
+) -> Vec<VestingPeriodResponse> {
+    let mut unlock_time = start_time;
+    let mut unlocks: Vec<VestingPeriodResponse> = vec![];
+    for (length, amount) in periods {
+        unlock_time += length;
+        unlocks.push(VestingPeriodResponse {
+            unlock_time: unlock_time as u64,
+            amount: amount.clone(),
+        });
+    }
+
+    match vesting_mode {
+        VestingMode::Continuous | VestingMode::Delayed if !unlocks.is_empty() => {
+            vec![VestingPeriodResponse {
+                unlock_time: unlock_time as u64,
+                amount: sum_coins(unlocks.iter().flat_map(|u| u.amount.iter())),
+            }]
+        }
+        _ => unlocks,
+    }
+}
+
 #[entry_point]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
-    // No state migrations performed, just returned a Response
-    Ok(Response::default())
+pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> StdResult<Response> {
+    match msg {
+        MigrateMsg::Migrate {} => {
+            let (from, to) = migrate_state(deps.storage)?;
+            Ok(Response::new().add_attributes(vec![
+                ("action", "migrate".to_string()),
+                ("from_version", from.to_string()),
+                ("to_version", to.to_string()),
+            ]))
+        }
+    }
 }
@@ -5,8 +5,8 @@ use sha2::Sha256;
 use sha3::{Digest, Keccak256};
 use std::convert::TryInto;
 
-/// Helpers for eth address and message verification
-/// Taking from: https://github.com/CosmWasm/cosmwasm/blob/main/contracts/crypto-verify/src/ethereum.rs
+// Helpers for eth address and message verification
+// Taking from: https://github.com/CosmWasm/cosmwasm/blob/main/contracts/crypto-verify/src/ethereum.rs
 
 /// Get the recovery param from the value `v` when no chain ID for replay protection is used.
 ///
@@ -86,16 +86,11 @@ pub fn compress_public_key(p: &[u8]) -> StdResult<[u8; 33]> {
     };
     let last_byte = [*last];
     let l = u8::from_be_bytes(last_byte);
-    let prefix: u8;
-    if l % 2 == 0 {
-        prefix = 2
-    } else {
-        prefix = 3
-    }
+    let prefix: u8 = if l % 2 == 0 { 2 } else { 3 };
     let mut ret = [0u8; 33];
     ret[1..].copy_from_slice(&data[..32]);
     ret[0] = prefix;
-    return Ok(ret);
+    Ok(ret)
 }
 
 pub fn public_key_to_address(k: &[u8], prefix: &str) -> StdResult<String> {
@@ -105,7 +100,7 @@ pub fn public_key_to_address(k: &[u8], prefix: &str) -> StdResult<String> {
     // let mut ripe_result: [u8; 20] = [0; 20];
     sha.update(k);
     let sha_result = sha.finalize();
-    hasher.update(&sha_result);
+    hasher.update(sha_result);
     let ripe_result = hasher.finalize();
     encode(
         prefix,
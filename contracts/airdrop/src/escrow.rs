@@ -0,0 +1,71 @@
+use cosmwasm_std::{Coin, StdResult, Storage, Uint128};
+
+use crate::state::{ClawbackInfo, Tranche, VestingSchedule, ESCROWED, VESTING_SCHEDULES};
+
+impl VestingSchedule {
+    pub fn total(&self) -> Uint128 {
+        self.tranches.iter().map(|t| t.amount).sum()
+    }
+
+    // Total amount unlocked at `time`, including what was already withdrawn
+    pub fn unlocked(&self, time: u64) -> Uint128 {
+        self.tranches
+            .iter()
+            .filter(|t| t.unlock_time <= time)
+            .map(|t| t.amount)
+            .sum()
+    }
+
+    pub fn withdrawable(&self, time: u64) -> Uint128 {
+        self.unlocked(time) - self.withdrawn
+    }
+
+    // Drops the tranches still locked at `time`, returning their total
+    pub fn revoke_locked(&mut self, time: u64) -> Uint128 {
+        let locked = self.total() - self.unlocked(time);
+        self.tranches.retain(|t| t.unlock_time <= time);
+        locked
+    }
+}
+
+// Appends the claimed tranches to the recipient's escrow schedule. Each
+// tranche unlocks once its period and all previous periods have elapsed.
+// Returns the amount placed in escrow
+pub fn record_schedule(
+    storage: &mut dyn Storage,
+    recipient: &str,
+    start_time: i64,
+    periods: &[(i64, Vec<Coin>)],
+    clawback: Option<ClawbackInfo>,
+) -> StdResult<Uint128> {
+    let mut schedule = VESTING_SCHEDULES
+        .may_load(storage, recipient)?
+        .unwrap_or_default();
+    if clawback.is_some() {
+        schedule.clawback = clawback;
+    }
+
+    let mut unlock_time = start_time;
+    let mut total = Uint128::zero();
+    for (length, coins) in periods {
+        unlock_time += length;
+        // Escrow campaigns distribute a single denom
+        let amount: Uint128 = coins.iter().map(|c| c.amount).sum();
+        if amount.is_zero() {
+            continue;
+        }
+        schedule.tranches.push(Tranche {
+            unlock_time: unlock_time as u64,
+            amount,
+        });
+        total += amount;
+    }
+
+    if !total.is_zero() {
+        VESTING_SCHEDULES.save(storage, recipient, &schedule)?;
+        let escrowed = ESCROWED.may_load(storage)?.unwrap_or_default();
+        ESCROWED.save(storage, &(escrowed + total))?;
+    }
+
+    Ok(total)
+}
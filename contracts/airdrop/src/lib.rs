@@ -5,7 +5,19 @@ pub mod state;
 pub mod submsg;
 pub mod verification;
 
+mod accounting;
+mod address;
+mod allocation;
+mod authz;
+mod bonus;
 mod distribution;
+mod escrow;
+mod migrations;
+mod pause;
+mod roles;
+mod screening;
+mod staking;
+mod sweep;
 mod vesting;
 
 #[cfg(test)]
@@ -0,0 +1,72 @@
+use cosmwasm_std::{StdError, StdResult, Storage};
+use cw2::{get_contract_version, set_contract_version};
+use cw_storage_plus::Item;
+use semver::Version;
+
+use crate::state::{CONFIG, MERKLE_ROOT};
+
+pub const CONTRACT_NAME: &str = "crates.io:airdrop";
+pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+// Deployments instantiated before cw2 was wired in carry no contract info
+const LEGACY_VERSION: &str = "0.0.0";
+
+type Migration = fn(&mut dyn Storage) -> StdResult<()>;
+
+// State migrations keyed by the version they bring state up to, oldest first
+const MIGRATIONS: &[(&str, Migration)] = &[("0.1.0", migrate_to_v0_1_0)];
+
+// Runs every migration between the stored version and this build, then
+// records the new version. Returns (from, to)
+pub fn migrate_state(storage: &mut dyn Storage) -> StdResult<(Version, Version)> {
+    let stored = match get_contract_version(storage) {
+        Ok(info) if info.contract != CONTRACT_NAME => {
+            return Err(StdError::generic_err(format!(
+                "cannot migrate from {}",
+                info.contract
+            )));
+        }
+        Ok(info) => info.version,
+        Err(StdError::NotFound { .. }) => LEGACY_VERSION.to_string(),
+        Err(err) => return Err(err),
+    };
+    let from = parse_version(&stored)?;
+    let to = parse_version(CONTRACT_VERSION)?;
+    if from > to {
+        return Err(StdError::generic_err(format!(
+            "cannot migrate from a newer version ({} > {})",
+            from, to
+        )));
+    }
+
+    for (version, migration) in MIGRATIONS {
+        let version = parse_version(version)?;
+        if from < version && version <= to {
+            migration(storage)?;
+        }
+    }
+    set_contract_version(storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok((from, to))
+}
+
+fn parse_version(version: &str) -> StdResult<Version> {
+    Version::parse(version)
+        .map_err(|_| StdError::generic_err(format!("invalid contract version: {}", version)))
+}
+
+// Stores the merkle root as raw bytes instead of a hex string, and rewrites
+// Config so fields added since instantiate are persisted with their defaults
+fn migrate_to_v0_1_0(storage: &mut dyn Storage) -> StdResult<()> {
+    const LEGACY_MERKLE_ROOT: Item<String> = Item::new("merkle_root");
+
+    if let Some(merkle_root) = LEGACY_MERKLE_ROOT.may_load(storage)? {
+        let mut root_buf: [u8; 32] = [0; 32];
+        hex::decode_to_slice(merkle_root, &mut root_buf)
+            .map_err(|_| StdError::generic_err("invalid merkle root"))?;
+        MERKLE_ROOT.save(storage, &root_buf)?;
+    }
+
+    let config = CONFIG.load(storage)?;
+    CONFIG.save(storage, &config)
+}
@@ -1,12 +1,20 @@
-use cosmwasm_std::Uint128;
+use cosmwasm_std::{Coin, Decimal, Uint128};
+use cw20::Cw20ReceiveMsg;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::state::{
+    ClaimBonus, PauseInfo, PendingSweep, Role, SweepDestination, TokenType, VestingFallback,
+    VestingMode,
+};
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
     pub admin: String,
     pub denom: String,
-    pub vesting_periods: [i64; 5],
+    // Length in seconds of each vesting tranche. A leaf must carry exactly
+    // one amount per tranche after its liquid amount
+    pub vesting_periods: Vec<i64>,
     // Only used for cosmos chains (ex. terra)
     pub prefix: Option<String>,
     // Start time from when the vesting starts. If None, then it will start
@@ -15,35 +23,192 @@ pub struct InstantiateMsg {
     // End time of the airdrop event. Afterwhich funds will be sent back to
     // the community pool
     pub claim_end_time: u64,
+    // Time claims open. If None, claims open right away
+    pub claim_start_time: Option<u64>,
     pub fee_refund: Option<Uint128>,
+    // Type of vesting account created for the vesting tranches. Defaults to
+    // periodic vesting
+    pub vesting_mode: Option<VestingMode>,
+    // Policy applied when the vesting account cannot be created. Defaults to
+    // holding the tranches in escrow
+    pub vesting_fallback: Option<VestingFallback>,
+    // Delay in seconds added in front of the first vesting period, so nothing
+    // unlocks before start time + cliff
+    pub cliff_seconds: Option<u64>,
+    // Treasury recorded as the funder of clawback allocations, which receives
+    // clawed back funds. If None, they go to the community pool
+    pub clawback_funder: Option<String>,
+    // Denoms distributed next to `denom`. Each leaf amount then lists one
+    // amount per denom separated by '|', starting with `denom`
+    pub extra_denoms: Option<Vec<String>>,
+    // Kind of token distributed. For CW20 campaigns `denom` is the token
+    // contract address. Defaults to native denoms
+    pub token_type: Option<TokenType>,
+    // Where End sends the leftover balance. Defaults to the community pool,
+    // or to the admin at the time of End for CW20 campaigns
+    pub sweep_destination: Option<SweepDestination>,
+    // Contract queried with `ScreeningQueryMsg` for every signer and
+    // recipient on top of the blocklist
+    pub screening_contract: Option<String>,
+    // Native denom of the gas pool. If set, fee refunds are paid from the
+    // pool instead of being deducted from allocations
+    pub gas_pool_denom: Option<String>,
+    // Early claim bonus, funded through FundBonusPool
+    pub claim_bonus: Option<ClaimBonus>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
     UpdateConfig {
-        admin: Option<String>,
         fee_refund: Option<Uint128>,
         enabled: Option<bool>,
+        // Takes effect after SWEEP_DESTINATION_DELAY
+        sweep_destination: Option<SweepDestination>,
+        // Can only move the end of the claim window later
+        claim_end_time: Option<u64>,
+    },
+    // Stops claims until Resume, or until `until` (in seconds) if set. Only
+    // the admin can set `until`
+    Pause {
+        reason: String,
+        until: Option<u64>,
+    },
+    Resume {},
+    // Proposes a new admin, who must accept before `expiry` (in seconds) to
+    // take over
+    ProposeAdmin {
+        admin: String,
+        expiry: Option<u64>,
+    },
+    AcceptAdmin {},
+    CancelAdminProposal {},
+    GrantRole {
+        role: Role,
+        address: String,
+    },
+    RevokeRole {
+        role: Role,
+        address: String,
     },
     RegisterMerkleRoot {
         merkle_root: String,
+        // Sum of every allocation in the tree, in `denom`. Enables the
+        // total check on claims and the liability in the Solvency query
+        total_amount: Option<Uint128>,
+    },
+    RegisterSchedule {
+        id: String,
+        periods: Vec<i64>,
+        cliff_seconds: Option<u64>,
+    },
+    UpdateValidators {
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
+    UpdateBlocklist {
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
+    // None stops querying the screening contract
+    UpdateScreeningContract {
+        address: Option<String>,
     },
     Claim {
         allocation: String,
         proofs: Vec<String>,
         message: String,
         signature: String,
+        // Validator the liquid amount is delegated to. The recipient must
+        // have granted the contract a MsgDelegate authorization
+        delegate_to: Option<String>,
     },
     End {},
+    Withdraw {},
+    Clawback {
+        recipient: String,
+    },
+    // Tops up the bonus pool with the attached `denom` funds. CW20
+    // campaigns use the FundBonusPool hook instead
+    FundBonusPool {},
+    // Tops up the gas pool with the attached `gas_pool_denom` funds
+    FundGasPool {},
+    // Sends `amount` of the gas pool, or all of it, back to the sender
+    WithdrawGasPool {
+        amount: Option<Uint128>,
+    },
+    // Funds a CW20 campaign
+    Receive(Cw20ReceiveMsg),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    Fund {},
+    FundBonusPool {},
+}
+
+// Interface the screening contract has to implement
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ScreeningQueryMsg {
+    IsBlocked { address: String },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ScreeningResponse {
+    pub blocked: bool,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
     Config {},
+    PendingAdmin {},
+    Roles {},
     MerkleRoot {},
-    IsClaimed { address: String },
+    IsClaimed {
+        address: String,
+    },
+    // At most MAX_ARE_CLAIMED addresses
+    AreClaimed {
+        addresses: Vec<String>,
+    },
+    VestingStatus {
+        address: String,
+    },
+    Schedule {
+        id: String,
+    },
+    SimulateClaim {
+        allocation: String,
+        proofs: Vec<String>,
+        message: String,
+        signature: String,
+        sender: String,
+    },
+    Validators {},
+    Blocklist {},
+    Solvency {},
+    GasPool {},
+    ClaimBonus {},
+    // Totals, with the daily claim counts starting after the day starting
+    // at `start_after` (in seconds)
+    Stats {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    // Claim records ordered by signer
+    ListClaims {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    // Claim records paying out to `recipient`, ordered by signer
+    ClaimsByRecipient {
+        recipient: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -59,6 +224,18 @@ pub struct ConfigResponse {
     pub denom: String,
     pub fee: Option<Uint128>,
     pub enabled: bool,
+    pub vesting_mode: VestingMode,
+    pub cliff_seconds: u64,
+    pub extra_denoms: Vec<String>,
+    pub token_type: TokenType,
+    pub sweep_destination: SweepDestination,
+    pub pending_sweep_destination: Option<PendingSweep>,
+    pub claim_start_time: u64,
+    pub claim_end_time: u64,
+    pub screening_contract: Option<String>,
+    pub gas_pool_denom: Option<String>,
+    pub claim_bonus: Option<ClaimBonus>,
+    pub pause: Option<PauseInfo>,
 }
 
 // We define a custom struct for each query response
@@ -67,8 +244,159 @@ pub struct MerkleRootResponse {
     pub merkle_root: String,
 }
 
+// We define a custom struct for each query response
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingAdminResponse {
+    pub admin: Option<String>,
+    pub expiry: Option<u64>,
+}
+
+// We define a custom struct for each query response
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RolesResponse {
+    pub roles: Vec<RoleResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RoleResponse {
+    pub role: Role,
+    pub address: String,
+}
+
 // We define a custom struct for each query response
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct IsClaimedResponse {
     pub is_claimed: bool,
 }
+
+// We define a custom struct for each query response
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AreClaimedResponse {
+    pub claims: Vec<ClaimStatusResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ClaimStatusResponse {
+    pub address: String,
+    pub is_claimed: bool,
+}
+
+// We define a custom struct for each query response
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VestingStatusResponse {
+    pub locked: Uint128,
+    pub unlocked: Uint128,
+    pub withdrawn: Uint128,
+}
+
+// We define a custom struct for each query response
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ScheduleResponse {
+    pub id: String,
+    pub periods: Vec<i64>,
+    pub cliff_seconds: u64,
+}
+
+// We define a custom struct for each query response
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SimulateClaimResponse {
+    pub recipient: Option<String>,
+    pub liquid: Vec<Coin>,
+    pub vesting: Vec<VestingPeriodResponse>,
+    pub fee_refund: Uint128,
+    // Bonus in `denom` included in the liquid and vesting amounts
+    pub bonus: Uint128,
+    // Reason the claim would fail, in which case nothing else is set
+    pub error: Option<String>,
+}
+
+// We define a custom struct for each query response
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ValidatorsResponse {
+    pub validators: Vec<String>,
+}
+
+// We define a custom struct for each query response
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ClaimsResponse {
+    pub claims: Vec<ClaimRecordResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ClaimRecordResponse {
+    pub signer: String,
+    pub recipient: String,
+    pub liquid: Vec<Coin>,
+    pub vesting: Vec<Coin>,
+    pub fee_refund: Uint128,
+    pub claimed_at: u64,
+    pub relayer: String,
+    pub gas_refund: Option<Coin>,
+}
+
+// We define a custom struct for each query response
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StatsResponse {
+    pub claims: u64,
+    pub liquid: Uint128,
+    pub vesting: Uint128,
+    pub fee_refunds: Uint128,
+    pub bonus: Uint128,
+    // Refunds paid by the gas pool, if there is one
+    pub gas_refunds: Option<Coin>,
+    pub daily: Vec<DailyClaimsResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DailyClaimsResponse {
+    // Start of the UTC day, in seconds
+    pub day: u64,
+    pub claims: u64,
+}
+
+// We define a custom struct for each query response
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SolvencyResponse {
+    pub denom: String,
+    pub balance: Uint128,
+    pub total_amount: Option<Uint128>,
+    pub claimed: Uint128,
+    pub refunded: Uint128,
+    pub escrowed: Uint128,
+    // Gas pool held in `denom`, zero when the pool uses another denom
+    pub gas_pool: Uint128,
+    pub bonus_pool: Uint128,
+    // Amount still owed: unclaimed allocations, escrowed tranches and the
+    // gas and bonus pools
+    pub liability: Uint128,
+    pub surplus: Uint128,
+    pub shortfall: Uint128,
+}
+
+// We define a custom struct for each query response
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GasPoolResponse {
+    pub denom: Option<String>,
+    pub balance: Uint128,
+}
+
+// We define a custom struct for each query response
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ClaimBonusResponse {
+    pub claim_bonus: Option<ClaimBonus>,
+    // Multiplier a claim made now would get before the pool cap
+    pub multiplier: Decimal,
+    pub pool: Uint128,
+}
+
+// We define a custom struct for each query response
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BlocklistResponse {
+    pub addresses: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VestingPeriodResponse {
+    pub unlock_time: u64,
+    pub amount: Vec<Coin>,
+}
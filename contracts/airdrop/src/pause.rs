@@ -0,0 +1,38 @@
+use cosmwasm_std::{StdError, StdResult, Storage};
+
+use crate::state::{Config, PauseInfo, PAUSE};
+
+pub enum PauseState {
+    Live,
+    // Disabled through UpdateConfig, without a reason
+    Disabled,
+    Paused(PauseInfo),
+}
+
+// State of the campaign at `time`. A pause whose scheduled resume has passed
+// is lifted without any transaction
+pub fn pause_state(storage: &dyn Storage, config: &Config, time: u64) -> StdResult<PauseState> {
+    if config.enabled {
+        return Ok(PauseState::Live);
+    }
+
+    match PAUSE.may_load(storage)? {
+        Some(pause) if matches!(pause.until, Some(until) if until <= time) => Ok(PauseState::Live),
+        Some(pause) => Ok(PauseState::Paused(pause)),
+        None => Ok(PauseState::Disabled),
+    }
+}
+
+pub fn assert_not_paused(storage: &dyn Storage, config: &Config, time: u64) -> StdResult<()> {
+    match pause_state(storage, config, time)? {
+        PauseState::Live => Ok(()),
+        PauseState::Disabled => Err(StdError::generic_err("airdrop event is disabled")),
+        PauseState::Paused(pause) => {
+            let mut msg = format!("airdrop event is paused: {}", pause.reason);
+            if let Some(until) = pause.until {
+                msg.push_str(&format!(" (resumes at {})", until));
+            }
+            Err(StdError::generic_err(msg))
+        }
+    }
+}
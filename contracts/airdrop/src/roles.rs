@@ -0,0 +1,27 @@
+use cosmwasm_std::{Addr, StdError, StdResult, Storage};
+
+use crate::state::{Config, Role, ROLES};
+
+impl Role {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::Pauser => "pauser",
+            Role::RootManager => "root_manager",
+            Role::Treasury => "treasury",
+        }
+    }
+}
+
+// The admin holds every role on top of the ones it grants
+pub fn assert_role(
+    storage: &dyn Storage,
+    config: &Config,
+    sender: &Addr,
+    role: Role,
+) -> StdResult<()> {
+    if *sender == config.admin || ROLES.has(storage, (role.as_str(), sender.as_str())) {
+        return Ok(());
+    }
+
+    Err(StdError::generic_err("unauthorized"))
+}
@@ -0,0 +1,35 @@
+use cosmwasm_std::{Deps, StdError, StdResult};
+
+use crate::msg::{ScreeningQueryMsg, ScreeningResponse};
+use crate::state::{Config, BLOCKLIST};
+
+fn blocked(address: &str, source: &str) -> StdError {
+    StdError::generic_err(format!("address blocked by {}: {}", source, address))
+}
+
+// Checks each normalized address against the admin blocklist first, then
+// against the screening contract if one is configured. The error names the
+// address and which of the two flagged it
+pub fn assert_not_blocked(deps: Deps, config: &Config, addresses: &[&str]) -> StdResult<()> {
+    for address in addresses {
+        if BLOCKLIST.has(deps.storage, address) {
+            return Err(blocked(address, "blocklist"));
+        }
+    }
+
+    if let Some(contract) = &config.screening_contract {
+        for address in addresses {
+            let res: ScreeningResponse = deps.querier.query_wasm_smart(
+                contract,
+                &ScreeningQueryMsg::IsBlocked {
+                    address: address.to_string(),
+                },
+            )?;
+            if res.blocked {
+                return Err(blocked(address, "screening"));
+            }
+        }
+    }
+
+    Ok(())
+}
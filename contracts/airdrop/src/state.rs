@@ -1,4 +1,4 @@
-use cosmwasm_std::Uint128;
+use cosmwasm_std::{Coin, Decimal, Uint128};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -10,13 +10,292 @@ pub struct Config {
     pub denom: String,
     pub prefix: Option<String>,
     pub start_time: Option<i64>,
-    pub vesting_periods: [i64; 5],
+    pub vesting_periods: Vec<i64>,
     pub claim_end_time: u64,
     pub fee_refund: Option<Uint128>,
     pub enabled: bool,
+    #[serde(default)]
+    pub vesting_mode: VestingMode,
+    #[serde(default)]
+    pub vesting_fallback: VestingFallback,
+    #[serde(default)]
+    pub cliff_seconds: u64,
+    #[serde(default)]
+    pub clawback_funder: Option<String>,
+    #[serde(default)]
+    pub extra_denoms: Vec<String>,
+    #[serde(default)]
+    pub token_type: TokenType,
+    #[serde(default)]
+    pub sweep_destination: SweepDestination,
+    #[serde(default)]
+    pub claim_start_time: u64,
+    #[serde(default)]
+    pub screening_contract: Option<String>,
+    #[serde(default)]
+    pub gas_pool_denom: Option<String>,
+    #[serde(default)]
+    pub claim_bonus: Option<ClaimBonus>,
 }
 
+impl Config {
+    // Campaign denoms in the order leaf amounts list them
+    pub fn denoms(&self) -> impl Iterator<Item = &String> {
+        std::iter::once(&self.denom).chain(self.extra_denoms.iter())
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum VestingMode {
+    // Each tranche unlocks at the end of its own period
+    // (MsgCreatePeriodicVestingAccount)
+    Periodic,
+    // The sum of all tranches unlocks linearly until the end of the last
+    // period (MsgCreateVestingAccount with delayed = false)
+    Continuous,
+    // The sum of all tranches unlocks at once at the end of the last period
+    // (MsgCreateVestingAccount with delayed = true)
+    Delayed,
+    // Tranches are held by the contract and released to the recipient
+    // through Withdraw as they unlock
+    Escrow,
+    // Like escrow, but the admin can claw back the tranches that are still
+    // locked
+    Clawback,
+}
+
+// Written out because deriving it with #[default] needs a newer toolchain
+// than the one in the optimizer image
+#[allow(clippy::derivable_impls)]
+impl Default for VestingMode {
+    fn default() -> Self {
+        VestingMode::Periodic
+    }
+}
+
+// Kind of token a campaign distributes
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenType {
+    // Native denoms sent with BankMsg
+    Native,
+    // A CW20 token whose contract address is the campaign denom. The contract
+    // is funded through the Receive hook and always vests through escrow
+    Cw20,
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for TokenType {
+    fn default() -> Self {
+        TokenType::Native
+    }
+}
+
+// Where End sends the balance left over after the campaign
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SweepDestination {
+    // MsgFundCommunityPool, only for native denoms
+    CommunityPool,
+    Treasury { address: String },
+    // A follow-up airdrop contract, funded through its Receive hook for CW20
+    Airdrop { address: String },
+    Burn,
+    // Whoever is admin when End runs
+    Admin,
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for SweepDestination {
+    fn default() -> Self {
+        SweepDestination::CommunityPool
+    }
+}
+
+// What the campaign owes in `denom`. The total covers every leaf of the
+// registered tree, fee refunds included
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct Accounting {
+    pub total_amount: Option<Uint128>,
+    pub claimed: Uint128,
+    pub refunded: Uint128,
+}
+
+// What a signer claimed, kept for support and listings
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ClaimRecord {
+    pub recipient: String,
+    pub liquid: Vec<Coin>,
+    pub vesting: Vec<Coin>,
+    // Refund deducted from the allocation, in `denom`
+    pub fee_refund: Uint128,
+    pub claimed_at: u64,
+    // Account that submitted the claim transaction
+    pub relayer: String,
+    // Refund paid by the gas pool instead
+    #[serde(default)]
+    pub gas_refund: Option<Coin>,
+}
+
+// Claim counters for dashboards, amounts in `denom`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct Stats {
+    pub claims: u64,
+    pub liquid: Uint128,
+    pub vesting: Uint128,
+    pub fee_refunds: Uint128,
+    #[serde(default)]
+    pub bonus: Uint128,
+    // Paid by the gas pool, in `gas_pool_denom`
+    #[serde(default)]
+    pub gas_refunds: Uint128,
+}
+
+// Multiplier on allocations in `denom`, decaying linearly from `multiplier`
+// at `start_time` to 1x at `end_time`. The extra comes from the bonus pool
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ClaimBonus {
+    pub multiplier: Decimal,
+    pub start_time: u64,
+    pub end_time: u64,
+}
+
+// Why claims are paused, and when they resume on their own if ever
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PauseInfo {
+    pub reason: String,
+    pub paused_at: u64,
+    pub until: Option<u64>,
+}
+
+// Sweep destination change waiting for its delay to pass
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingSweep {
+    pub destination: SweepDestination,
+    pub effective_time: u64,
+}
+
+// What happens to the vesting tranches when the chain rejects the vesting
+// account message, e.g. because the recipient account already exists
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum VestingFallback {
+    // Hold the tranches in contract escrow, released through Withdraw
+    Escrow,
+    // Send the tranches to the recipient right away
+    Liquid,
+    // Revert the whole claim
+    Revert,
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for VestingFallback {
+    fn default() -> Self {
+        VestingFallback::Escrow
+    }
+}
+
+// Vesting account being created by the current claim, kept around for the
+// fallback in reply
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingVesting {
+    pub recipient: String,
+    pub start_time: i64,
+    pub periods: Vec<(i64, Vec<Coin>)>,
+}
+
+// Permissions the admin can grant to other accounts
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    // Can disable claims
+    Pauser,
+    // Can register merkle roots and vesting schedules
+    RootManager,
+    // Can change the fee refund, end the airdrop and claw back vesting
+    Treasury,
+}
+
+// Admin proposed by the current admin, who takes over once they accept
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingAdmin {
+    pub admin: String,
+    pub expiry: Option<u64>,
+}
+
+// Vesting timeline registered by the admin, which leaves refer to by id
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Schedule {
+    pub periods: Vec<i64>,
+    pub cliff_seconds: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Tranche {
+    pub unlock_time: u64,
+    pub amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct VestingSchedule {
+    pub tranches: Vec<Tranche>,
+    pub withdrawn: Uint128,
+    // Set when the admin can claw back the locked tranches
+    #[serde(default)]
+    pub clawback: Option<ClawbackInfo>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ClawbackInfo {
+    // Account that funded the allocation and receives the clawed back funds.
+    // None sends them to the community pool
+    pub funder: Option<String>,
+}
+
+// Upper bound on the number of vesting tranches a campaign can configure,
+// which also bounds the size of the generated vesting message
+pub const MAX_VESTING_PERIODS: usize = 60;
+// Upper bound on the cliff plus all vesting periods (10 years)
+pub const MAX_VESTING_DURATION: u64 = 315_360_000;
+// Delay before a sweep destination change takes effect (3 days)
+pub const SWEEP_DESTINATION_DELAY: u64 = 259_200;
+
 pub const CONFIG: Item<Config> = Item::new("config");
 
-pub const MERKLE_ROOT: Item<String> = Item::new("merkle_root");
+pub const PENDING_ADMIN: Item<PendingAdmin> = Item::new("pending_admin");
+pub const PENDING_SWEEP: Item<PendingSweep> = Item::new("pending_sweep");
+pub const PAUSE: Item<PauseInfo> = Item::new("pause");
+// Roles granted by the admin, keyed by (role, address)
+pub const ROLES: Map<(&str, &str), Role> = Map::new("roles");
+
+// Stored as raw bytes since 0.1.0, it used to be a hex string
+pub const MERKLE_ROOT: Item<[u8; 32]> = Item::new("merkle_root");
+// Keyed by normalized signer, see address::normalize_address
 pub const CLAIM_INDEX: Map<&str, bool> = Map::new("claim_index");
+// Claims made since records were introduced, by normalized signer
+pub const CLAIM_RECORDS: Map<&str, ClaimRecord> = Map::new("claim_records");
+// Reverse index of CLAIM_RECORDS, keyed by (recipient, signer)
+pub const RECIPIENT_CLAIMS: Map<(&str, &str), bool> = Map::new("recipient_claims");
+pub const SCHEDULES: Map<&str, Schedule> = Map::new("schedules");
+// Validators claims can delegate to, managed by the admin
+pub const VALIDATORS: Map<&str, bool> = Map::new("validators");
+// Normalized signer and recipient addresses barred from claiming
+pub const BLOCKLIST: Map<&str, bool> = Map::new("blocklist");
+
+// Escrowed vesting schedules by recipient address
+pub const VESTING_SCHEDULES: Map<&str, VestingSchedule> = Map::new("vesting_schedules");
+// Escrowed amount still owed to recipients, excluded from the end sweep
+pub const ESCROWED: Item<Uint128> = Item::new("escrowed");
+// Sponsored fee refund balance, in `Config.gas_pool_denom`
+pub const GAS_POOL: Item<Uint128> = Item::new("gas_pool");
+// Early claim bonus balance, in `denom`
+pub const BONUS_POOL: Item<Uint128> = Item::new("bonus_pool");
+pub const PENDING_VESTING: Item<PendingVesting> = Item::new("pending_vesting");
+pub const ACCOUNTING: Item<Accounting> = Item::new("accounting");
+
+pub const SECONDS_PER_DAY: u64 = 86_400;
+
+pub const STATS: Item<Stats> = Item::new("stats");
+// Number of claims by UTC day, keyed by days since the unix epoch
+pub const DAILY_CLAIMS: Map<u64, u64> = Map::new("daily_claims");
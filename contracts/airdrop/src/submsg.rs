@@ -1,115 +1,228 @@
+use crate::allocation::{amount_of, sum_coins};
+use crate::authz::{Any, MsgExec};
 use crate::distribution::{Coin as DistributionCoin, MsgFundCommunityPool};
-use crate::vesting::{Coin as VestingCoin, MsgCreatePeriodicVestingAccount, Period};
-use cosmwasm_std::{coins, BankMsg, Binary, CosmosMsg, Env, Response, StdResult, Uint128};
+use crate::staking::{Coin as StakingCoin, MsgDelegate};
+use crate::state::{TokenType, VestingMode};
+use crate::vesting::{
+    Coin as VestingCoin, MsgCreatePeriodicVestingAccount, MsgCreateVestingAccount, Period,
+};
+use cosmwasm_std::{
+    coins, to_binary, BankMsg, Binary, Coin, CosmosMsg, Env, Response, StdResult, SubMsg, Uint128,
+    WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
 use protobuf::Message;
 
-pub fn create_fund_community_pool_response(
-    denom: String,
-    depositor: String,
-    amount: Uint128,
-) -> StdResult<Response> {
+// Reply sent back when the vesting account message fails
+pub const CREATE_VESTING_ACCOUNT_REPLY_ID: u64 = 1;
+// Reply sent back when delegating the liquid amount fails
+pub const DELEGATE_REPLY_ID: u64 = 2;
+
+pub fn create_fund_community_pool_msg(depositor: String, amount: Vec<Coin>) -> CosmosMsg {
     let mut msg = MsgFundCommunityPool::new();
-    let mut coin = DistributionCoin::new();
-    coin.amount = amount.to_string();
-    coin.denom = denom.clone();
-    msg.amount = vec![coin];
+    msg.amount = amount
+        .into_iter()
+        .map(|c| {
+            let mut coin = DistributionCoin::new();
+            coin.amount = c.amount.to_string();
+            coin.denom = c.denom;
+            coin
+        })
+        .collect();
     msg.depositor = depositor;
     let bytes = Message::write_to_bytes(&msg).unwrap();
 
-    Ok(Response::default().add_message(CosmosMsg::Stargate {
+    CosmosMsg::Stargate {
         type_url: "/cosmos.distribution.v1beta1.MsgFundCommunityPool".to_string(),
         value: Binary(bytes),
-    }))
+    }
+}
+
+// Delegates on behalf of the delegator, using the MsgDelegate authorization
+// it granted to the contract
+pub fn create_delegate_msg(
+    grantee: String,
+    delegator: String,
+    validator: String,
+    amount: Coin,
+) -> CosmosMsg {
+    let mut coin = StakingCoin::new();
+    coin.denom = amount.denom;
+    coin.amount = amount.amount.to_string();
+    let mut delegate = MsgDelegate::new();
+    delegate.delegator_address = delegator;
+    delegate.validator_address = validator;
+    delegate.amount = Some(coin).into();
+
+    let mut any = Any::new();
+    any.type_url = "/cosmos.staking.v1beta1.MsgDelegate".to_string();
+    any.value = Message::write_to_bytes(&delegate).unwrap();
+    let mut msg = MsgExec::new();
+    msg.grantee = grantee;
+    msg.msgs = vec![any];
+
+    CosmosMsg::Stargate {
+        type_url: "/cosmos.authz.v1beta1.MsgExec".to_string(),
+        value: Binary(Message::write_to_bytes(&msg).unwrap()),
+    }
 }
 
+// Sends campaign tokens, with a bank transfer for native denoms or a
+// transfer on the token contract for CW20
+pub fn create_send_msgs(
+    token_type: &TokenType,
+    recipient: String,
+    amount: Vec<Coin>,
+) -> StdResult<Vec<CosmosMsg>> {
+    match token_type {
+        TokenType::Native => Ok(vec![CosmosMsg::Bank(BankMsg::Send {
+            to_address: recipient,
+            amount,
+        })]),
+        TokenType::Cw20 => amount
+            .into_iter()
+            .map(|coin| {
+                Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: coin.denom,
+                    msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                        recipient: recipient.clone(),
+                        amount: coin.amount,
+                    })?,
+                    funds: vec![],
+                }))
+            })
+            .collect(),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn create_claim_response(
     env: Env,
     sender: String,
+    token_type: TokenType,
     denom: String,
     claimer: String,
     recipient: String,
-    vested: u128,
-    periods: Vec<(i64, String)>,
+    vested: Vec<Coin>,
+    periods: Vec<(i64, Vec<Coin>)>,
     start_time: Option<i64>,
+    vesting_mode: VestingMode,
     refund_amount: Uint128,
 ) -> StdResult<Response> {
     create_vesting_account(
         env,
         sender,
+        token_type,
         denom,
         claimer,
         recipient,
         vested,
         periods,
         start_time,
+        vesting_mode,
         refund_amount,
     )
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn create_vesting_account(
     env: Env,
     sender: String,
+    token_type: TokenType,
     denom: String,
     claimer: String,
     recipient: String,
-    vested: u128,
-    periods: Vec<(i64, String)>,
+    vested: Vec<Coin>,
+    periods: Vec<(i64, Vec<Coin>)>,
     start_time: Option<i64>,
+    vesting_mode: VestingMode,
     refund_amount: Uint128,
 ) -> StdResult<Response> {
-    let mut msg = MsgCreatePeriodicVestingAccount::new();
-    msg.from_address = env.contract.address.to_string();
-    msg.to_address = recipient.clone();
-    msg.start_time = match start_time {
+    let start_time = match start_time {
         Some(t) => t,
         None => env.block.time.seconds() as i64,
     };
-    let mut total_vesting = 0u128;
-    msg.vesting_periods = periods
+    let total_vesting = sum_coins(periods.iter().flat_map(|v| v.1.iter()));
+    let vesting_periods = periods
         .iter()
         .map(|v| {
-            let coins: Vec<VestingCoin> = if v.1.eq("0") {
-                vec![]
-            } else {
-                let mut coin = VestingCoin::new();
-                coin.denom = denom.clone();
-                coin.amount = v.1.clone();
-                vec![coin]
-            };
-            let amount_u128 = u128::from_str_radix(&v.1.clone(), 10).unwrap_or_else(|_| 0u128);
-            total_vesting += amount_u128;
             let mut period = Period::new();
             period.length = v.0;
-            period.amount = coins;
+            period.amount = to_vesting_coins(&v.1);
 
             period
         })
         .collect::<Vec<Period>>();
-    let bytes = Message::write_to_bytes(&msg).unwrap();
-    let mut msgs: Vec<CosmosMsg> = vec![];
-    if vested > 0 {
-        msgs.push(CosmosMsg::Bank(BankMsg::Send {
-            to_address: recipient.clone(),
-            amount: coins(vested, denom.clone()),
-        }))
+    let vesting_msg = match vesting_mode {
+        VestingMode::Periodic => {
+            let mut msg = MsgCreatePeriodicVestingAccount::new();
+            msg.from_address = env.contract.address.to_string();
+            msg.to_address = recipient.clone();
+            msg.start_time = start_time;
+            msg.vesting_periods = vesting_periods;
+            Some(CosmosMsg::Stargate {
+                type_url: "/cosmos.vesting.v1beta1.MsgCreatePeriodicVestingAccount".to_string(),
+                value: Binary(Message::write_to_bytes(&msg).unwrap()),
+            })
+        }
+        VestingMode::Continuous | VestingMode::Delayed => {
+            // The account starts vesting at the block time, so the schedule
+            // collapses to the total amount unlocking by the end of the last period.
+            // Instantiate rejects cliffs and future start times for continuous
+            // accounts, which could not be honored here
+            let mut msg = MsgCreateVestingAccount::new();
+            msg.from_address = env.contract.address.to_string();
+            msg.to_address = recipient.clone();
+            msg.amount = to_vesting_coins(&total_vesting);
+            msg.end_time = start_time + periods.iter().map(|v| v.0).sum::<i64>();
+            msg.delayed = vesting_mode == VestingMode::Delayed;
+            Some(CosmosMsg::Stargate {
+                type_url: "/cosmos.vesting.v1beta1.MsgCreateVestingAccount".to_string(),
+                value: Binary(Message::write_to_bytes(&msg).unwrap()),
+            })
+        }
+        // Tranches were recorded in contract storage and stay in escrow
+        VestingMode::Escrow | VestingMode::Clawback => None,
+    };
+    let mut msgs: Vec<SubMsg> = vec![];
+    if !vested.is_empty() {
+        for msg in create_send_msgs(&token_type, recipient.clone(), vested.clone())? {
+            msgs.push(SubMsg::new(msg));
+        }
     }
-    if total_vesting > 0 {
-        msgs.push(CosmosMsg::Stargate {
-            type_url: "/cosmos.vesting.v1beta1.MsgCreatePeriodicVestingAccount".to_string(),
-            value: Binary(bytes),
-        });
+    if let Some(vesting_msg) = vesting_msg {
+        if !total_vesting.is_empty() {
+            // A failure is handled in reply so the claim itself still goes
+            // through. Success replies too, to drop the pending schedule
+            msgs.push(SubMsg::reply_always(
+                vesting_msg,
+                CREATE_VESTING_ACCOUNT_REPLY_ID,
+            ));
+        }
     }
     if !refund_amount.is_zero() {
-        msgs.push(CosmosMsg::Bank(BankMsg::Send {
-            to_address: sender.clone(),
-            amount: coins(refund_amount.u128(), denom.clone()),
-        }));
+        let refund = coins(refund_amount.u128(), denom.clone());
+        for msg in create_send_msgs(&token_type, sender.clone(), refund)? {
+            msgs.push(SubMsg::new(msg));
+        }
     }
-    Ok(Response::new().add_messages(msgs).add_attributes(vec![
+    Ok(Response::new().add_submessages(msgs).add_attributes(vec![
         ("action", "claim"),
         ("address", &claimer.to_string()),
         ("new_address", &recipient.to_string()),
-        ("vested", &vested.to_string()),
-        ("vesting", &total_vesting.to_string()),
+        ("vested", &amount_of(&vested, &denom).to_string()),
+        ("vesting", &amount_of(&total_vesting, &denom).to_string()),
     ]))
 }
+
+fn to_vesting_coins(coins: &[Coin]) -> Vec<VestingCoin> {
+    coins
+        .iter()
+        .map(|c| {
+            let mut coin = VestingCoin::new();
+            coin.denom = c.denom.clone();
+            coin.amount = c.amount.to_string();
+            coin
+        })
+        .collect()
+}
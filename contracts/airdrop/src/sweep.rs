@@ -0,0 +1,125 @@
+use cosmwasm_std::{
+    to_binary, Api, BankMsg, Coin, CosmosMsg, StdError, StdResult, Storage, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+
+use crate::msg::Cw20HookMsg;
+use crate::state::{Config, SweepDestination, TokenType, PENDING_SWEEP};
+use crate::submsg::create_fund_community_pool_msg;
+
+impl SweepDestination {
+    pub fn route(&self) -> &'static str {
+        match self {
+            SweepDestination::CommunityPool => "community_pool",
+            SweepDestination::Treasury { .. } => "treasury",
+            SweepDestination::Airdrop { .. } => "airdrop",
+            SweepDestination::Burn => "burn",
+            SweepDestination::Admin => "admin",
+        }
+    }
+
+    // Destination End actually sends to, with `Admin` resolved to `admin`
+    pub fn resolve(self, admin: &str) -> SweepDestination {
+        match self {
+            SweepDestination::Admin => SweepDestination::Treasury {
+                address: admin.to_string(),
+            },
+            destination => destination,
+        }
+    }
+
+    pub fn address(&self) -> Option<&str> {
+        match self {
+            SweepDestination::Treasury { address } | SweepDestination::Airdrop { address } => {
+                Some(address)
+            }
+            _ => None,
+        }
+    }
+}
+
+// Normalizes the destination addresses and rejects routes the campaign token
+// can't take
+pub fn validate_sweep_destination(
+    api: &dyn Api,
+    token_type: &TokenType,
+    destination: SweepDestination,
+) -> StdResult<SweepDestination> {
+    match destination {
+        SweepDestination::CommunityPool if *token_type == TokenType::Cw20 => Err(
+            StdError::generic_err("the community pool only takes native denoms"),
+        ),
+        SweepDestination::Treasury { address } => Ok(SweepDestination::Treasury {
+            address: api.addr_validate(&address)?.to_string(),
+        }),
+        SweepDestination::Airdrop { address } => Ok(SweepDestination::Airdrop {
+            address: api.addr_validate(&address)?.to_string(),
+        }),
+        destination => Ok(destination),
+    }
+}
+
+// Destination in effect at `time`. A pending change takes over once its
+// delay has passed
+pub fn effective_sweep_destination(
+    storage: &dyn Storage,
+    config: &Config,
+    time: u64,
+) -> StdResult<SweepDestination> {
+    match PENDING_SWEEP.may_load(storage)? {
+        Some(pending) if pending.effective_time <= time => Ok(pending.destination),
+        _ => Ok(config.sweep_destination.clone()),
+    }
+}
+
+pub fn create_sweep_msgs(
+    token_type: &TokenType,
+    destination: &SweepDestination,
+    contract_address: String,
+    amount: Vec<Coin>,
+) -> StdResult<Vec<CosmosMsg>> {
+    match (destination, token_type) {
+        (SweepDestination::Admin, _) => Err(StdError::generic_err(
+            "sweep destination must be resolved first",
+        )),
+        (SweepDestination::CommunityPool, _) => Ok(vec![create_fund_community_pool_msg(
+            contract_address,
+            amount,
+        )]),
+        (SweepDestination::Treasury { address }, TokenType::Native)
+        | (SweepDestination::Airdrop { address }, TokenType::Native) => {
+            Ok(vec![CosmosMsg::Bank(BankMsg::Send {
+                to_address: address.clone(),
+                amount,
+            })])
+        }
+        (SweepDestination::Burn, TokenType::Native) => {
+            Ok(vec![CosmosMsg::Bank(BankMsg::Burn { amount })])
+        }
+        (destination, TokenType::Cw20) => amount
+            .into_iter()
+            .map(|coin| {
+                let msg = match destination {
+                    SweepDestination::Treasury { address } => Cw20ExecuteMsg::Transfer {
+                        recipient: address.clone(),
+                        amount: coin.amount,
+                    },
+                    // The follow-up airdrop is funded through its Receive hook
+                    SweepDestination::Airdrop { address } => Cw20ExecuteMsg::Send {
+                        contract: address.clone(),
+                        amount: coin.amount,
+                        msg: to_binary(&Cw20HookMsg::Fund {})?,
+                    },
+                    _ => Cw20ExecuteMsg::Burn {
+                        amount: coin.amount,
+                    },
+                };
+                Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: coin.denom,
+                    msg: to_binary(&msg)?,
+                    funds: vec![],
+                }))
+            })
+            .collect(),
+    }
+}
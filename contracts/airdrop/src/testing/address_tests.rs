@@ -0,0 +1,51 @@
+use crate::address::{normalize_address, to_checksum_address};
+use cosmwasm_std::StdError;
+
+#[test]
+fn normalize_evm_address() {
+    let lowercase = "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed";
+    assert_eq!(
+        to_checksum_address(lowercase),
+        "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"
+    );
+
+    assert_eq!(normalize_address(lowercase).unwrap(), lowercase);
+    assert_eq!(
+        normalize_address("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed").unwrap(),
+        lowercase
+    );
+    assert_eq!(
+        normalize_address("0x5AAEB6053F3E94C9B9A09F33669435E7EF1BEAED").unwrap(),
+        lowercase
+    );
+    assert_eq!(
+        normalize_address("0x5AAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"),
+        Err(StdError::generic_err(
+            "invalid EIP-55 checksum: 0x5AAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"
+        ))
+    );
+    assert!(normalize_address("0x5aaeb6053f3e94c9b9a09f33669435e7ef1bea").is_err());
+}
+
+#[test]
+fn normalize_bech32_address() {
+    let address = "terra1352nvukmex9ax4c329cp7ln9jfgjf0z7whvfca";
+    assert_eq!(normalize_address(address).unwrap(), address);
+    assert_eq!(normalize_address(&address.to_uppercase()).unwrap(), address);
+    assert_eq!(
+        normalize_address("inj1mlv7s4rpyzakjq29mf78hnjczdez0s45fc8esh").unwrap(),
+        "inj1mlv7s4rpyzakjq29mf78hnjczdez0s45fc8esh"
+    );
+}
+
+#[test]
+fn normalize_base58_address() {
+    // base58 is case sensitive, solana public keys are kept as they are
+    let address = "9TV9dQpFg1MPbDmPQjX9jmJgDpHHu8pt7Ezj6TcmoWzV";
+    assert_eq!(normalize_address(address).unwrap(), address);
+
+    assert_eq!(
+        normalize_address("terra1invalid"),
+        Err(StdError::generic_err("invalid address: terra1invalid"))
+    );
+}
@@ -1,2 +1,3 @@
+mod address_tests;
 mod tests;
 mod verification_tests;
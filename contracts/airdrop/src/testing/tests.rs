@@ -1,18 +1,61 @@
-use crate::contract::{execute, instantiate, query};
+use crate::contract::{execute, instantiate, migrate, query};
 use crate::distribution::{Coin as DistributionCoin, MsgFundCommunityPool};
 use crate::msg::{
-    ConfigResponse, ExecuteMsg, InstantiateMsg, IsClaimedResponse, MerkleRootResponse, QueryMsg,
+    ConfigResponse, ExecuteMsg, InstantiateMsg, IsClaimedResponse, MerkleRootResponse, MigrateMsg,
+    PendingAdminResponse, QueryMsg, RoleResponse, RolesResponse,
+};
+use crate::state::{
+    PauseInfo, PendingSweep, Role, SweepDestination, TokenType, VestingFallback, VestingMode,
+    ESCROWED,
 };
-use crate::vesting::{Coin as VestingCoin, MsgCreatePeriodicVestingAccount, Period};
 use cosmwasm_std::testing::{
     mock_dependencies, mock_dependencies_with_balance, mock_env, mock_info,
 };
 use cosmwasm_std::{
-    attr, coins, from_binary, BankMsg, Binary, Coin, CosmosMsg, StdError, SubMsg, Timestamp,
-    Uint128,
+    attr, coins, from_binary, to_vec, BankMsg, Binary, Coin, CosmosMsg, Event, StdError, Storage,
+    SubMsg, Timestamp, Uint128,
 };
+use cw2::{get_contract_version, set_contract_version, ContractVersion};
 use protobuf::Message;
 
+#[cfg(any(feature = "terra", feature = "solana"))]
+use sha3::Digest;
+
+// Vesting account messages are only checked by the chains creating them
+#[cfg(any(feature = "terra", feature = "eth", feature = "cosmos"))]
+use crate::submsg::CREATE_VESTING_ACCOUNT_REPLY_ID;
+#[cfg(any(feature = "terra", feature = "eth", feature = "cosmos"))]
+use crate::vesting::{Coin as VestingCoin, MsgCreatePeriodicVestingAccount, Period};
+
+#[cfg(feature = "terra")]
+use crate::authz::{Any, MsgExec};
+#[cfg(feature = "terra")]
+use crate::contract::reply;
+#[cfg(feature = "terra")]
+use crate::msg::{
+    AreClaimedResponse, BlocklistResponse, ClaimBonusResponse, ClaimRecordResponse,
+    ClaimStatusResponse, ClaimsResponse, Cw20HookMsg, DailyClaimsResponse, GasPoolResponse,
+    ScheduleResponse, ScreeningQueryMsg, ScreeningResponse, SimulateClaimResponse,
+    SolvencyResponse, StatsResponse, ValidatorsResponse, VestingPeriodResponse,
+    VestingStatusResponse,
+};
+#[cfg(feature = "terra")]
+use crate::staking::{Coin as StakingCoin, MsgDelegate};
+#[cfg(feature = "terra")]
+use crate::state::ClaimBonus;
+#[cfg(feature = "terra")]
+use crate::submsg::DELEGATE_REPLY_ID;
+#[cfg(feature = "terra")]
+use crate::vesting::MsgCreateVestingAccount;
+#[cfg(feature = "terra")]
+use cosmwasm_std::{
+    to_binary, ContractResult, Decimal, Reply, Response, SubMsgResponse, SubMsgResult,
+    SystemResult, WasmMsg, WasmQuery,
+};
+#[cfg(feature = "terra")]
+use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20ReceiveMsg};
+
+#[cfg(any(feature = "terra", feature = "eth", feature = "cosmos"))]
 fn to_period(v: &(i64, String)) -> Period {
     let coins: Vec<VestingCoin> = if v.1.eq("0") {
         vec![]
@@ -30,6 +73,12 @@ fn to_period(v: &(i64, String)) -> Period {
     period
 }
 
+// Root of a tree holding a single leaf, which needs no proofs
+#[cfg(any(feature = "terra", feature = "solana"))]
+fn single_leaf_root(allocation: &str) -> String {
+    hex::encode(sha3::Keccak256::digest(allocation.as_bytes()))
+}
+
 #[test]
 fn proper_instantiate() {
     let mut deps = mock_dependencies();
@@ -37,7 +86,7 @@ fn proper_instantiate() {
     let msg = InstantiateMsg {
         admin: "admin0000".to_string(),
         denom: "uluna".to_string(),
-        vesting_periods: [
+        vesting_periods: vec![
             15552000i64,
             15552000i64,
             46656000i64,
@@ -48,6 +97,17 @@ fn proper_instantiate() {
         prefix: None,
         claim_end_time: 1955870000u64,
         fee_refund: None,
+        vesting_mode: None,
+        vesting_fallback: None,
+        cliff_seconds: None,
+        clawback_funder: None,
+        extra_denoms: None,
+        token_type: None,
+        sweep_destination: None,
+        claim_start_time: None,
+        screening_contract: None,
+        gas_pool_denom: None,
+        claim_bonus: None,
     };
 
     let info = mock_info("addr0000", &[]);
@@ -70,7 +130,7 @@ fn invalid_instantiation() {
     let msg = InstantiateMsg {
         admin: "admin0000".to_string(),
         denom: "uluna".to_string(),
-        vesting_periods: [
+        vesting_periods: vec![
             -15552000i64,
             15552000i64,
             46656000i64,
@@ -81,6 +141,17 @@ fn invalid_instantiation() {
         prefix: None,
         claim_end_time: 1955870000u64,
         fee_refund: None,
+        vesting_mode: None,
+        vesting_fallback: None,
+        cliff_seconds: None,
+        clawback_funder: None,
+        extra_denoms: None,
+        token_type: None,
+        sweep_destination: None,
+        claim_start_time: None,
+        screening_contract: None,
+        gas_pool_denom: None,
+        claim_bonus: None,
     };
 
     assert_eq!(
@@ -91,7 +162,7 @@ fn invalid_instantiation() {
     let msg = InstantiateMsg {
         admin: "admin0000".to_string(),
         denom: "uluna".to_string(),
-        vesting_periods: [
+        vesting_periods: vec![
             15552000i64,
             15552000i64,
             46656000i64,
@@ -102,6 +173,17 @@ fn invalid_instantiation() {
         prefix: None,
         claim_end_time: 1955870000u64,
         fee_refund: None,
+        vesting_mode: None,
+        vesting_fallback: None,
+        cliff_seconds: None,
+        clawback_funder: None,
+        extra_denoms: None,
+        token_type: None,
+        sweep_destination: None,
+        claim_start_time: None,
+        screening_contract: None,
+        gas_pool_denom: None,
+        claim_bonus: None,
     };
 
     assert_eq!(
@@ -112,7 +194,7 @@ fn invalid_instantiation() {
     let msg = InstantiateMsg {
         admin: "admin0000".to_string(),
         denom: "uluna".to_string(),
-        vesting_periods: [
+        vesting_periods: vec![
             15552000i64,
             15552000i64,
             46656000i64,
@@ -123,6 +205,17 @@ fn invalid_instantiation() {
         prefix: None,
         claim_end_time: mock_env().block.time.seconds() - 100,
         fee_refund: None,
+        vesting_mode: None,
+        vesting_fallback: None,
+        cliff_seconds: None,
+        clawback_funder: None,
+        extra_denoms: None,
+        token_type: None,
+        sweep_destination: None,
+        claim_start_time: None,
+        screening_contract: None,
+        gas_pool_denom: None,
+        claim_bonus: None,
     };
 
     assert_eq!(
@@ -131,6 +224,90 @@ fn invalid_instantiation() {
             "claim_end_time must be in the future"
         ))
     );
+
+    let msg = InstantiateMsg {
+        admin: "admin0000".to_string(),
+        denom: "uluna".to_string(),
+        vesting_periods: vec![2592000i64; 61],
+        start_time: None,
+        prefix: None,
+        claim_end_time: 1955870000u64,
+        fee_refund: None,
+        vesting_mode: None,
+        vesting_fallback: None,
+        cliff_seconds: None,
+        clawback_funder: None,
+        extra_denoms: None,
+        token_type: None,
+        sweep_destination: None,
+        claim_start_time: None,
+        screening_contract: None,
+        gas_pool_denom: None,
+        claim_bonus: None,
+    };
+
+    assert_eq!(
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg),
+        Err(StdError::generic_err(
+            "too many vesting periods, maximum is 60"
+        ))
+    );
+
+    let msg = InstantiateMsg {
+        admin: "admin0000".to_string(),
+        denom: "uluna".to_string(),
+        vesting_periods: vec![31536000i64; 9],
+        start_time: None,
+        prefix: None,
+        claim_end_time: 1955870000u64,
+        fee_refund: None,
+        vesting_mode: None,
+        vesting_fallback: None,
+        cliff_seconds: Some(31536001u64),
+        clawback_funder: None,
+        extra_denoms: None,
+        token_type: None,
+        sweep_destination: None,
+        claim_start_time: None,
+        screening_contract: None,
+        gas_pool_denom: None,
+        claim_bonus: None,
+    };
+
+    assert_eq!(
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg),
+        Err(StdError::generic_err(
+            "vesting schedule too long, maximum is 315360000 seconds"
+        ))
+    );
+
+    let msg = InstantiateMsg {
+        admin: "admin0000".to_string(),
+        denom: "uluna".to_string(),
+        vesting_periods: vec![],
+        start_time: None,
+        prefix: None,
+        claim_end_time: 1955870000u64,
+        fee_refund: None,
+        vesting_mode: None,
+        vesting_fallback: None,
+        cliff_seconds: Some(15552000u64),
+        clawback_funder: None,
+        extra_denoms: None,
+        token_type: None,
+        sweep_destination: None,
+        claim_start_time: None,
+        screening_contract: None,
+        gas_pool_denom: None,
+        claim_bonus: None,
+    };
+
+    assert_eq!(
+        instantiate(deps.as_mut(), mock_env(), info, msg),
+        Err(StdError::generic_err(
+            "cliff requires at least one vesting period"
+        ))
+    );
 }
 
 #[test]
@@ -140,7 +317,7 @@ fn update_config() {
     let msg = InstantiateMsg {
         admin: "admin0000".to_string(),
         denom: "uluna".to_string(),
-        vesting_periods: [
+        vesting_periods: vec![
             15552000i64,
             15552000i64,
             46656000i64,
@@ -151,17 +328,29 @@ fn update_config() {
         prefix: None,
         claim_end_time: 1955870000u64,
         fee_refund: None,
+        vesting_mode: None,
+        vesting_fallback: None,
+        cliff_seconds: None,
+        clawback_funder: None,
+        extra_denoms: None,
+        token_type: None,
+        sweep_destination: None,
+        claim_start_time: None,
+        screening_contract: None,
+        gas_pool_denom: None,
+        claim_bonus: None,
     };
 
     let info = mock_info("addr0000", &[]);
     let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
 
-    // update admin and fee
+    // update fee
     let info = mock_info("admin0000", &[]);
     let msg = ExecuteMsg::UpdateConfig {
-        admin: Some("admin0001".to_string()),
         fee_refund: Some(Uint128::new(10000)),
         enabled: None,
+        sweep_destination: None,
+        claim_end_time: None,
     };
 
     let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
@@ -173,19 +362,32 @@ fn update_config() {
     assert_eq!(
         config,
         ConfigResponse {
-            admin: "admin0001".to_string(),
+            admin: "admin0000".to_string(),
             denom: "uluna".to_string(),
             fee: Some(Uint128::new(10000)),
-            enabled: true
+            enabled: true,
+            vesting_mode: VestingMode::Periodic,
+            cliff_seconds: 0,
+            extra_denoms: vec![],
+            token_type: TokenType::Native,
+            sweep_destination: SweepDestination::CommunityPool,
+            pending_sweep_destination: None,
+            claim_start_time: 0,
+            claim_end_time: 1955870000u64,
+            screening_contract: None,
+            gas_pool_denom: None,
+            claim_bonus: None,
+            pause: None,
         },
     );
 
     // Unauthorized err
-    let info = mock_info("admin0000", &[]);
+    let info = mock_info("addr0000", &[]);
     let msg = ExecuteMsg::UpdateConfig {
-        admin: None,
         fee_refund: None,
         enabled: None,
+        sweep_destination: None,
+        claim_end_time: None,
     };
 
     let res = execute(deps.as_mut(), mock_env(), info, msg);
@@ -193,6 +395,116 @@ fn update_config() {
         Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "unauthorized"),
         _ => panic!("Must return unauthorized error"),
     }
+
+    // admin transfers moved to ProposeAdmin, the old payload must not pass
+    let info = mock_info("admin0000", &[]);
+    let msg: ExecuteMsg = from_binary(&Binary::from(
+        br#"{"update_config":{"admin":"admin0001"}}"#.as_ref(),
+    ))
+    .unwrap();
+    assert_eq!(
+        execute(deps.as_mut(), mock_env(), info, msg),
+        Err(StdError::generic_err("no config field to update"))
+    );
+}
+
+#[test]
+fn migrate_from_legacy() {
+    let mut deps = mock_dependencies();
+
+    // state written by deployments without cw2 contract info
+    let root = "634de21cde1044f41d90373733b0f0fb1c1c71f9652b905cdf159e73c4cf0d37";
+    deps.storage.set(
+        b"config",
+        br#"{"admin":"admin0000","denom":"uluna","prefix":null,"start_time":null,"vesting_periods":[],"claim_end_time":1955870000,"fee_refund":null,"enabled":true}"#,
+    );
+    deps.storage
+        .set(b"merkle_root", &to_vec(&root.to_string()).unwrap());
+
+    let res = migrate(deps.as_mut(), mock_env(), MigrateMsg::Migrate {}).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "migrate"),
+            attr("from_version", "0.0.0"),
+            attr("to_version", env!("CARGO_PKG_VERSION")),
+        ]
+    );
+    assert_eq!(
+        get_contract_version(&deps.storage).unwrap().version,
+        env!("CARGO_PKG_VERSION")
+    );
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::MerkleRoot {}).unwrap();
+    let merkle_root: MerkleRootResponse = from_binary(&res).unwrap();
+    assert_eq!(merkle_root.merkle_root, root);
+
+    // fields added after the deployment are persisted with their defaults
+    let config = String::from_utf8(deps.storage.get(b"config").unwrap()).unwrap();
+    assert!(config.contains(r#""vesting_mode":"periodic""#));
+    assert!(config.contains(r#""claim_start_time":0"#));
+
+    // migrating again is a no-op
+    let res = migrate(deps.as_mut(), mock_env(), MigrateMsg::Migrate {}).unwrap();
+    assert_eq!(
+        res.attributes[1],
+        attr("from_version", env!("CARGO_PKG_VERSION"))
+    );
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::MerkleRoot {}).unwrap();
+    let merkle_root: MerkleRootResponse = from_binary(&res).unwrap();
+    assert_eq!(merkle_root.merkle_root, root);
+}
+
+#[test]
+fn migrate_version_checks() {
+    let mut deps = mock_dependencies();
+
+    let msg = InstantiateMsg {
+        admin: "admin0000".to_string(),
+        denom: "uluna".to_string(),
+        vesting_periods: vec![],
+        start_time: None,
+        prefix: None,
+        claim_end_time: 1955870000u64,
+        fee_refund: None,
+        vesting_mode: None,
+        vesting_fallback: None,
+        cliff_seconds: None,
+        clawback_funder: None,
+        extra_denoms: None,
+        token_type: None,
+        sweep_destination: None,
+        claim_start_time: None,
+        screening_contract: None,
+        gas_pool_denom: None,
+        claim_bonus: None,
+    };
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(
+        get_contract_version(&deps.storage).unwrap(),
+        ContractVersion {
+            contract: "crates.io:airdrop".to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+        }
+    );
+
+    set_contract_version(&mut deps.storage, "crates.io:airdrop", "99.0.0").unwrap();
+    assert_eq!(
+        migrate(deps.as_mut(), mock_env(), MigrateMsg::Migrate {}),
+        Err(StdError::generic_err(format!(
+            "cannot migrate from a newer version (99.0.0 > {})",
+            env!("CARGO_PKG_VERSION")
+        )))
+    );
+
+    set_contract_version(&mut deps.storage, "crates.io:cw20-base", "0.1.0").unwrap();
+    assert_eq!(
+        migrate(deps.as_mut(), mock_env(), MigrateMsg::Migrate {}),
+        Err(StdError::generic_err(
+            "cannot migrate from crates.io:cw20-base"
+        ))
+    );
 }
 
 #[test]
@@ -202,7 +514,7 @@ fn update_invalid_config() {
     let msg = InstantiateMsg {
         admin: "admin0000".to_string(),
         denom: "uluna".to_string(),
-        vesting_periods: [
+        vesting_periods: vec![
             15552000i64,
             15552000i64,
             46656000i64,
@@ -213,16 +525,26 @@ fn update_invalid_config() {
         prefix: None,
         claim_end_time: 1955870000u64,
         fee_refund: None,
+        vesting_mode: None,
+        vesting_fallback: None,
+        cliff_seconds: None,
+        clawback_funder: None,
+        extra_denoms: None,
+        token_type: None,
+        sweep_destination: None,
+        claim_start_time: None,
+        screening_contract: None,
+        gas_pool_denom: None,
+        claim_bonus: None,
     };
 
     let info = mock_info("addr0000", &[]);
     let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
 
     let info = mock_info("admin0000", &[]);
-    let msg = ExecuteMsg::UpdateConfig {
-        admin: Some("".to_string()),
-        fee_refund: Some(Uint128::new(10000)),
-        enabled: None,
+    let msg = ExecuteMsg::ProposeAdmin {
+        admin: "".to_string(),
+        expiry: None,
     };
 
     assert_eq!(
@@ -233,6 +555,369 @@ fn update_invalid_config() {
     );
 }
 
+#[test]
+fn transfer_admin() {
+    let mut deps = mock_dependencies();
+
+    let msg = InstantiateMsg {
+        admin: "admin0000".to_string(),
+        denom: "uluna".to_string(),
+        vesting_periods: vec![],
+        start_time: None,
+        prefix: None,
+        claim_end_time: 1955870000u64,
+        fee_refund: None,
+        vesting_mode: None,
+        vesting_fallback: None,
+        cliff_seconds: None,
+        clawback_funder: None,
+        extra_denoms: None,
+        token_type: None,
+        sweep_destination: None,
+        claim_start_time: None,
+        screening_contract: None,
+        gas_pool_denom: None,
+        claim_bonus: None,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let pending_admin = |deps: cosmwasm_std::Deps| -> PendingAdminResponse {
+        from_binary(&query(deps, mock_env(), QueryMsg::PendingAdmin {}).unwrap()).unwrap()
+    };
+    let expiry = mock_env().block.time.seconds() + 100;
+    let propose = ExecuteMsg::ProposeAdmin {
+        admin: "admin0001".to_string(),
+        expiry: Some(expiry),
+    };
+
+    assert_eq!(
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin0001", &[]),
+            propose.clone()
+        ),
+        Err(StdError::generic_err("unauthorized"))
+    );
+    assert_eq!(
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin0000", &[]),
+            ExecuteMsg::ProposeAdmin {
+                admin: "admin0001".to_string(),
+                expiry: Some(mock_env().block.time.seconds()),
+            }
+        ),
+        Err(StdError::generic_err("expiry must be in the future"))
+    );
+
+    // a cancelled proposal can't be accepted
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin0000", &[]),
+        propose.clone(),
+    )
+    .unwrap();
+    assert_eq!(
+        pending_admin(deps.as_ref()),
+        PendingAdminResponse {
+            admin: Some("admin0001".to_string()),
+            expiry: Some(expiry),
+        }
+    );
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin0000", &[]),
+        ExecuteMsg::CancelAdminProposal {},
+    )
+    .unwrap();
+    assert_eq!(
+        pending_admin(deps.as_ref()),
+        PendingAdminResponse {
+            admin: None,
+            expiry: None,
+        }
+    );
+    assert_eq!(
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin0001", &[]),
+            ExecuteMsg::AcceptAdmin {}
+        ),
+        Err(StdError::generic_err("no pending admin"))
+    );
+
+    // nor an expired one
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin0000", &[]),
+        propose,
+    )
+    .unwrap();
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(expiry);
+    assert_eq!(
+        execute(
+            deps.as_mut(),
+            env,
+            mock_info("admin0001", &[]),
+            ExecuteMsg::AcceptAdmin {}
+        ),
+        Err(StdError::generic_err("admin proposal expired"))
+    );
+
+    // only the proposed admin can accept
+    assert_eq!(
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin0002", &[]),
+            ExecuteMsg::AcceptAdmin {}
+        ),
+        Err(StdError::generic_err("unauthorized"))
+    );
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin0001", &[]),
+        ExecuteMsg::AcceptAdmin {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "accept_admin"),
+            attr("previous_admin", "admin0000"),
+            attr("admin", "admin0001"),
+        ]
+    );
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(config.admin, "admin0001");
+    assert_eq!(
+        pending_admin(deps.as_ref()),
+        PendingAdminResponse {
+            admin: None,
+            expiry: None,
+        }
+    );
+}
+
+#[test]
+fn role_permissions() {
+    let mut deps = mock_dependencies();
+
+    let msg = InstantiateMsg {
+        admin: "admin0000".to_string(),
+        denom: "uluna".to_string(),
+        vesting_periods: vec![],
+        start_time: None,
+        prefix: None,
+        claim_end_time: 1955870000u64,
+        fee_refund: None,
+        vesting_mode: None,
+        vesting_fallback: None,
+        cliff_seconds: None,
+        clawback_funder: None,
+        extra_denoms: None,
+        token_type: None,
+        sweep_destination: None,
+        claim_start_time: None,
+        screening_contract: None,
+        gas_pool_denom: None,
+        claim_bonus: None,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // only the admin grants roles
+    assert_eq!(
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("pauser0000", &[]),
+            ExecuteMsg::GrantRole {
+                role: Role::Pauser,
+                address: "pauser0000".to_string(),
+            }
+        ),
+        Err(StdError::generic_err("unauthorized"))
+    );
+    for (role, address) in [
+        (Role::Pauser, "pauser0000"),
+        (Role::RootManager, "multisig0000"),
+        (Role::Treasury, "dao0000"),
+    ] {
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin0000", &[]),
+            ExecuteMsg::GrantRole {
+                role: role.clone(),
+                address: address.to_string(),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            res.attributes,
+            vec![
+                attr("action", "grant_role"),
+                attr("role", role.as_str()),
+                attr("address", address),
+            ]
+        );
+    }
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Roles {}).unwrap();
+    let roles: RolesResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        roles.roles,
+        vec![
+            RoleResponse {
+                role: Role::Pauser,
+                address: "pauser0000".to_string(),
+            },
+            RoleResponse {
+                role: Role::Treasury,
+                address: "dao0000".to_string(),
+            },
+            RoleResponse {
+                role: Role::RootManager,
+                address: "multisig0000".to_string(),
+            },
+        ]
+    );
+
+    let update_config =
+        |fee_refund: Option<u128>, enabled: Option<bool>| ExecuteMsg::UpdateConfig {
+            fee_refund: fee_refund.map(Uint128::new),
+            enabled,
+            sweep_destination: None,
+            claim_end_time: None,
+        };
+    let register_root = ExecuteMsg::RegisterMerkleRoot {
+        merkle_root: "85e33930e7a8f015316cb4a53a4c45d26a69f299fc4c83f17357e1fd62e8fd95".to_string(),
+        total_amount: None,
+    };
+
+    // the pauser can stop claims but not resume them
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("pauser0000", &[]),
+        update_config(None, Some(false)),
+    )
+    .unwrap();
+    assert_eq!(
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("pauser0000", &[]),
+            update_config(None, Some(true))
+        ),
+        Err(StdError::generic_err("unauthorized"))
+    );
+    assert_eq!(
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("pauser0000", &[]),
+            register_root.clone()
+        ),
+        Err(StdError::generic_err("unauthorized"))
+    );
+
+    // the root manager registers roots but can't touch the fee
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("multisig0000", &[]),
+        register_root,
+    )
+    .unwrap();
+    assert_eq!(
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("multisig0000", &[]),
+            update_config(Some(100), None)
+        ),
+        Err(StdError::generic_err("unauthorized"))
+    );
+
+    // the treasury sets the fee and ends the airdrop
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("dao0000", &[]),
+        update_config(Some(100), None),
+    )
+    .unwrap();
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(1955870001u64);
+    assert_eq!(
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("multisig0000", &[]),
+            ExecuteMsg::End {}
+        ),
+        Err(StdError::generic_err("unauthorized"))
+    );
+    let _res = execute(
+        deps.as_mut(),
+        env,
+        mock_info("dao0000", &[]),
+        ExecuteMsg::End {},
+    )
+    .unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(config.fee, Some(Uint128::new(100)));
+    assert!(!config.enabled);
+
+    // a revoked role loses its permissions
+    let revoke = ExecuteMsg::RevokeRole {
+        role: Role::Pauser,
+        address: "pauser0000".to_string(),
+    };
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin0000", &[]),
+        revoke.clone(),
+    )
+    .unwrap();
+    assert_eq!(
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("pauser0000", &[]),
+            update_config(None, Some(false))
+        ),
+        Err(StdError::generic_err("unauthorized"))
+    );
+    assert_eq!(
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin0000", &[]),
+            revoke
+        ),
+        Err(StdError::generic_err("role not granted"))
+    );
+}
+
 #[test]
 fn disable_airdrop_contract() {
     let mut deps = mock_dependencies();
@@ -240,7 +925,7 @@ fn disable_airdrop_contract() {
     let msg = InstantiateMsg {
         admin: "admin0000".to_string(),
         denom: "uluna".to_string(),
-        vesting_periods: [
+        vesting_periods: vec![
             15552000i64,
             15552000i64,
             46656000i64,
@@ -251,17 +936,29 @@ fn disable_airdrop_contract() {
         prefix: None,
         claim_end_time: 1955870000u64,
         fee_refund: None,
+        vesting_mode: None,
+        vesting_fallback: None,
+        cliff_seconds: None,
+        clawback_funder: None,
+        extra_denoms: None,
+        token_type: None,
+        sweep_destination: None,
+        claim_start_time: None,
+        screening_contract: None,
+        gas_pool_denom: None,
+        claim_bonus: None,
     };
 
     let info = mock_info("addr0000", &[]);
     let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
 
-    // update admin and fee
+    // disable the airdrop
     let info = mock_info("admin0000", &[]);
     let msg = ExecuteMsg::UpdateConfig {
-        admin: Some("admin0001".to_string()),
         fee_refund: None,
         enabled: Some(false),
+        sweep_destination: None,
+        claim_end_time: None,
     };
 
     let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
@@ -273,10 +970,22 @@ fn disable_airdrop_contract() {
     assert_eq!(
         config,
         ConfigResponse {
-            admin: "admin0001".to_string(),
+            admin: "admin0000".to_string(),
             denom: "uluna".to_string(),
             fee: None,
-            enabled: false
+            enabled: false,
+            vesting_mode: VestingMode::Periodic,
+            cliff_seconds: 0,
+            extra_denoms: vec![],
+            token_type: TokenType::Native,
+            sweep_destination: SweepDestination::CommunityPool,
+            pending_sweep_destination: None,
+            claim_start_time: 0,
+            claim_end_time: 1955870000u64,
+            screening_contract: None,
+            gas_pool_denom: None,
+            claim_bonus: None,
+            pause: None,
         },
     );
     let msg = ExecuteMsg::Claim {
@@ -288,6 +997,7 @@ fn disable_airdrop_contract() {
         ],
         message: "terra1gtf24wp9fvpupaykl6sskkc6mw8c5l4wny5fhk".to_string(),
         signature: "cac2f150692e11a108ff05a75f364d245cf7e322cdc847555cdada5b3ba7dfc7200f37110b48752e6813b2f02361e26edf3e129ba7930ab60b996daa6f7dd9b11c".to_string(),
+        delegate_to: None,
     };
 
     let info = mock_info("terra1qfqa2eu9wp272ha93lj4yhcenrc6ymng079nu8", &[]);
@@ -303,7 +1013,7 @@ fn register_merkle_root() {
     let msg = InstantiateMsg {
         admin: "admin0000".to_string(),
         denom: "uluna".to_string(),
-        vesting_periods: [
+        vesting_periods: vec![
             15552000i64,
             15552000i64,
             46656000i64,
@@ -314,6 +1024,17 @@ fn register_merkle_root() {
         prefix: None,
         claim_end_time: 1955870000u64,
         fee_refund: None,
+        vesting_mode: None,
+        vesting_fallback: None,
+        cliff_seconds: None,
+        clawback_funder: None,
+        extra_denoms: None,
+        token_type: None,
+        sweep_destination: None,
+        claim_start_time: None,
+        screening_contract: None,
+        gas_pool_denom: None,
+        claim_bonus: None,
     };
 
     let info = mock_info("addr0000", &[]);
@@ -323,6 +1044,7 @@ fn register_merkle_root() {
     let info = mock_info("admin0000", &[]);
     let msg = ExecuteMsg::RegisterMerkleRoot {
         merkle_root: "634de21cde1044f41d90373733b0f0fb1c1c71f9652b905cdf159e73c4cf0d37".to_string(),
+        total_amount: None,
     };
 
     let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
@@ -348,12 +1070,12 @@ fn register_merkle_root() {
 #[cfg(feature = "eth")]
 #[test]
 fn claim_eth() {
-    let mut deps = mock_dependencies();
+    let mut deps = mock_dependencies_with_balance(&coins(1_000_000_000, "uluna"));
 
     let msg = InstantiateMsg {
         admin: "admin0000".to_string(),
         denom: "uluna".to_string(),
-        vesting_periods: [
+        vesting_periods: vec![
             15552000i64,
             15552000i64,
             46656000i64,
@@ -364,6 +1086,17 @@ fn claim_eth() {
         prefix: None,
         claim_end_time: 1955870000u64,
         fee_refund: Some(Uint128::new(100)),
+        vesting_mode: None,
+        vesting_fallback: None,
+        cliff_seconds: None,
+        clawback_funder: None,
+        extra_denoms: None,
+        token_type: None,
+        sweep_destination: None,
+        claim_start_time: None,
+        screening_contract: None,
+        gas_pool_denom: None,
+        claim_bonus: None,
     };
 
     let info = mock_info("addr0000", &[]);
@@ -374,6 +1107,7 @@ fn claim_eth() {
     let info = mock_info("admin0000", &[]);
     let msg = ExecuteMsg::RegisterMerkleRoot {
         merkle_root: "aef38d9db282ffdcf070ea04c771442f64e6a93d93aa9dd0f2a25a52ea57e48d".to_string(),
+        total_amount: None,
     };
     let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
 
@@ -386,6 +1120,7 @@ fn claim_eth() {
         ],
         message: "terra1gtf24wp9fvpupaykl6sskkc6mw8c5l4wny5fhk".to_string(),
         signature: "cac2f150692e11a108ff05a75f364d245cf7e322cdc847555cdada5b3ba7dfc7200f37110b48752e6813b2f02361e26edf3e129ba7930ab60b996daa6f7dd9b11c".to_string(),
+        delegate_to: None,
     };
 
     let info = mock_info("terra1qfqa2eu9wp272ha93lj4yhcenrc6ymng079nu8", &[]);
@@ -410,10 +1145,13 @@ fn claim_eth() {
 
     assert_eq!(
         res.messages[0],
-        SubMsg::new(CosmosMsg::Stargate {
-            type_url: "/cosmos.vesting.v1beta1.MsgCreatePeriodicVestingAccount".to_string(),
-            value: Binary(bytes),
-        })
+        SubMsg::reply_always(
+            CosmosMsg::Stargate {
+                type_url: "/cosmos.vesting.v1beta1.MsgCreatePeriodicVestingAccount".to_string(),
+                value: Binary(bytes),
+            },
+            CREATE_VESTING_ACCOUNT_REPLY_ID,
+        )
     );
     assert_eq!(
         res.messages[1],
@@ -461,13 +1199,13 @@ fn claim_eth() {
 
 #[cfg(feature = "eth")]
 #[test]
-fn claim_eth_incorrect_signature() {
-    let mut deps = mock_dependencies();
+fn claim_eth_delegate_by_relayer() {
+    let mut deps = mock_dependencies_with_balance(&coins(1_000_000_000, "uluna"));
 
     let msg = InstantiateMsg {
         admin: "admin0000".to_string(),
         denom: "uluna".to_string(),
-        vesting_periods: [
+        vesting_periods: vec![
             15552000i64,
             15552000i64,
             46656000i64,
@@ -477,7 +1215,90 @@ fn claim_eth_incorrect_signature() {
         start_time: None,
         prefix: None,
         claim_end_time: 1955870000u64,
-        fee_refund: Some(Uint128::new(100)),
+        fee_refund: None,
+        vesting_mode: None,
+        vesting_fallback: None,
+        cliff_seconds: None,
+        clawback_funder: None,
+        extra_denoms: None,
+        token_type: None,
+        sweep_destination: None,
+        claim_start_time: None,
+        screening_contract: None,
+        gas_pool_denom: None,
+        claim_bonus: None,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let info = mock_info("admin0000", &[]);
+    let msg = ExecuteMsg::RegisterMerkleRoot {
+        merkle_root: "aef38d9db282ffdcf070ea04c771442f64e6a93d93aa9dd0f2a25a52ea57e48d".to_string(),
+        total_amount: None,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    let msg = ExecuteMsg::UpdateValidators {
+        add: vec!["terravaloper1validator".to_string()],
+        remove: vec![],
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let msg = ExecuteMsg::Claim {
+        allocation: "0x78864ce3e53a439ae0a8e15622aa0d21675ad4cd,0,1000,12000,0,100000,0".to_string(),
+        proofs: vec![
+            "cbcae9860f77d0d6a3ba13892c8de9daf7a5505878fd35a4f82ce161bdbf4ae8".to_string(),
+            "47e6a6ada4d2a53b6b78835a73d194758694968e9f17be7260acf3f12dee1d42".to_string(),
+            "e677d3688a7cc4aaedc4c49aa510f8a1b01553f02b4524bbf79bc3cef6ac47ea".to_string(),
+        ],
+        message: "terra1gtf24wp9fvpupaykl6sskkc6mw8c5l4wny5fhk".to_string(),
+        signature: "cac2f150692e11a108ff05a75f364d245cf7e322cdc847555cdada5b3ba7dfc7200f37110b48752e6813b2f02361e26edf3e129ba7930ab60b996daa6f7dd9b11c".to_string(),
+        delegate_to: Some("terravaloper1validator".to_string()),
+    };
+
+    // The signature doesn't cover the validator, so a relayer can't pick it
+    let info = mock_info("terra1qfqa2eu9wp272ha93lj4yhcenrc6ymng079nu8", &[]);
+    assert_eq!(
+        execute(deps.as_mut(), mock_env(), info, msg.clone()),
+        Err(StdError::generic_err(
+            "delegate_to can only be set by the recipient"
+        ))
+    );
+
+    let info = mock_info("terra1gtf24wp9fvpupaykl6sskkc6mw8c5l4wny5fhk", &[]);
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+}
+
+#[cfg(feature = "eth")]
+#[test]
+fn claim_eth_incorrect_signature() {
+    let mut deps = mock_dependencies();
+
+    let msg = InstantiateMsg {
+        admin: "admin0000".to_string(),
+        denom: "uluna".to_string(),
+        vesting_periods: vec![
+            15552000i64,
+            15552000i64,
+            46656000i64,
+            15552000i64,
+            62208000i64,
+        ],
+        start_time: None,
+        prefix: None,
+        claim_end_time: 1955870000u64,
+        fee_refund: Some(Uint128::new(100)),
+        vesting_mode: None,
+        vesting_fallback: None,
+        cliff_seconds: None,
+        clawback_funder: None,
+        extra_denoms: None,
+        token_type: None,
+        sweep_destination: None,
+        claim_start_time: None,
+        screening_contract: None,
+        gas_pool_denom: None,
+        claim_bonus: None,
     };
 
     let info = mock_info("addr0000", &[]);
@@ -488,6 +1309,7 @@ fn claim_eth_incorrect_signature() {
     let info = mock_info("admin0000", &[]);
     let msg = ExecuteMsg::RegisterMerkleRoot {
         merkle_root: "aef38d9db282ffdcf070ea04c771442f64e6a93d93aa9dd0f2a25a52ea57e48d".to_string(),
+        total_amount: None,
     };
     let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
 
@@ -501,6 +1323,7 @@ fn claim_eth_incorrect_signature() {
         message: "terra1gtf24wp9fvpupaykl6sskkc6mw8c5l4wny5fhk".to_string(),
         // original: cac2f150692e11a108ff05a75f364d245cf7e322cdc847555cdada5b3ba7dfc7200f37110b48752e6813b2f02361e26edf3e129ba7930ab60b996daa6f7dd9b11c
         signature: "cac2f150692e11a108ff05a75f364d245cf1e322cdc847555cdada5b3ba7dfc7200f37110b48752e6813b2f02361e26edf3e129ba7930ab60b996daa6f7dd9b11c".to_string(),
+        delegate_to: None,
     };
 
     let info = mock_info("terra1qfqa2eu9wp272ha93lj4yhcenrc6ymng079nu8", &[]);
@@ -518,7 +1341,7 @@ fn is_claimed_cosmos() {
     let msg = InstantiateMsg {
         admin: "admin0000".to_string(),
         denom: "uluna".to_string(),
-        vesting_periods: [
+        vesting_periods: vec![
             15552000i64,
             15552000i64,
             46656000i64,
@@ -529,6 +1352,17 @@ fn is_claimed_cosmos() {
         prefix: Some("inj".to_string()),
         claim_end_time: 1955870000u64,
         fee_refund: Some(Uint128::new(1)),
+        vesting_mode: None,
+        vesting_fallback: None,
+        cliff_seconds: None,
+        clawback_funder: None,
+        extra_denoms: None,
+        token_type: None,
+        sweep_destination: None,
+        claim_start_time: None,
+        screening_contract: None,
+        gas_pool_denom: None,
+        claim_bonus: None,
     };
     let info = mock_info("addr0000", &[]);
     let env = mock_env();
@@ -548,15 +1382,90 @@ fn is_claimed_cosmos() {
     );
 }
 
+#[cfg(feature = "solana")]
+#[test]
+fn claim_solana() {
+    let mut deps = mock_dependencies_with_balance(&coins(1_000_000_000, "uluna"));
+
+    let msg = InstantiateMsg {
+        admin: "admin0000".to_string(),
+        denom: "uluna".to_string(),
+        vesting_periods: vec![],
+        start_time: None,
+        prefix: None,
+        claim_end_time: 1955870000u64,
+        fee_refund: None,
+        vesting_mode: None,
+        vesting_fallback: None,
+        cliff_seconds: None,
+        clawback_funder: None,
+        extra_denoms: None,
+        token_type: None,
+        sweep_destination: None,
+        claim_start_time: None,
+        screening_contract: None,
+        gas_pool_denom: None,
+        claim_bonus: None,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let allocation = "62ckGY2ntsSBd1YCoyUPTuV4aWtcDEKNMeysg2xv9px8,100".to_string();
+    let msg = ExecuteMsg::RegisterMerkleRoot {
+        merkle_root: single_leaf_root(&allocation),
+        total_amount: None,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("admin0000", &[]), msg).unwrap();
+
+    let msg = ExecuteMsg::Claim {
+        allocation,
+        proofs: vec![],
+        message: "terra1zdpgj8am5nqqvht927k3etljyl6a52kwqup0je".to_string(),
+        signature: "8c78d40738fd257dffec56714ece1837d0a561aa6aaff741f2992e327aa3c2f493d3267a3041c45d524cd81ac613c70a440ea54dc8ef28da3fd9fa8433579401".to_string(),
+        delegate_to: None,
+    };
+    let info = mock_info("terra1qfqa2eu9wp272ha93lj4yhcenrc6ymng079nu8", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "terra1zdpgj8am5nqqvht927k3etljyl6a52kwqup0je".to_string(),
+            amount: coins(100, "uluna")
+        }))]
+    );
+    assert_eq!(
+        res.attributes[1],
+        attr("address", "62ckGY2ntsSBd1YCoyUPTuV4aWtcDEKNMeysg2xv9px8")
+    );
+
+    // Base58 keys are case sensitive, so a lowercased key is another account
+    let is_claimed = |address: &str| -> bool {
+        let msg = QueryMsg::IsClaimed {
+            address: address.to_string(),
+        };
+        from_binary::<IsClaimedResponse>(&query(deps.as_ref(), mock_env(), msg).unwrap())
+            .unwrap()
+            .is_claimed
+    };
+    assert!(is_claimed("62ckGY2ntsSBd1YCoyUPTuV4aWtcDEKNMeysg2xv9px8"));
+    assert!(!is_claimed("62ckgy2ntssbd1ycoyuptuv4awtcdeknmeysg2xv9px8"));
+
+    assert_eq!(
+        execute(deps.as_mut(), mock_env(), info, msg),
+        Err(StdError::generic_err("already claimed"))
+    );
+}
+
 #[cfg(feature = "cosmos")]
 #[test]
 fn claim_cosmos() {
-    let mut deps = mock_dependencies();
+    let mut deps = mock_dependencies_with_balance(&coins(1_000_000_000, "uluna"));
 
     let msg = InstantiateMsg {
         admin: "admin0000".to_string(),
         denom: "uluna".to_string(),
-        vesting_periods: [
+        vesting_periods: vec![
             15552000i64,
             15552000i64,
             46656000i64,
@@ -567,6 +1476,17 @@ fn claim_cosmos() {
         prefix: Some("kava".to_string()),
         claim_end_time: 1955870000u64,
         fee_refund: Some(Uint128::new(1)),
+        vesting_mode: None,
+        vesting_fallback: None,
+        cliff_seconds: None,
+        clawback_funder: None,
+        extra_denoms: None,
+        token_type: None,
+        sweep_destination: None,
+        claim_start_time: None,
+        screening_contract: None,
+        gas_pool_denom: None,
+        claim_bonus: None,
     };
 
     let info = mock_info("addr0000", &[]);
@@ -577,6 +1497,7 @@ fn claim_cosmos() {
     let info = mock_info("admin0000", &[]);
     let msg = ExecuteMsg::RegisterMerkleRoot {
         merkle_root: "72e0f34627f8520beab9a6e4ffbd793c03315a068f99800636a140c74b65bf9c".to_string(),
+        total_amount: None,
     };
     let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
 
@@ -590,6 +1511,7 @@ fn claim_cosmos() {
         ],
         message: "terra1gtf24wp9fvpupaykl6sskkc6mw8c5l4wny5fhk".to_string(),
         signature: "283de2b632fcad3f5eb10e0dea5c324cc7094cd0bc33a1b9a46021fb29b3812f5e1d4617c82cd53a6b5309a08349da34b7e24747b0b1f0bb48b668f815b46ec1".to_string(),
+        delegate_to: None,
     };
 
     let info = mock_info("terra1qfqa2eu9wp272ha93lj4yhcenrc6ymng079nu8", &[]);
@@ -621,10 +1543,13 @@ fn claim_cosmos() {
     );
     assert_eq!(
         res.messages[1],
-        SubMsg::new(CosmosMsg::Stargate {
-            type_url: "/cosmos.vesting.v1beta1.MsgCreatePeriodicVestingAccount".to_string(),
-            value: Binary(bytes),
-        })
+        SubMsg::reply_always(
+            CosmosMsg::Stargate {
+                type_url: "/cosmos.vesting.v1beta1.MsgCreatePeriodicVestingAccount".to_string(),
+                value: Binary(bytes),
+            },
+            CREATE_VESTING_ACCOUNT_REPLY_ID,
+        )
     );
     assert_eq!(
         res.messages[2],
@@ -673,12 +1598,12 @@ fn claim_cosmos() {
 #[cfg(feature = "terra")]
 #[test]
 fn claim_terra() {
-    let mut deps = mock_dependencies();
+    let mut deps = mock_dependencies_with_balance(&coins(1_000_000_000, "uluna"));
 
     let msg = InstantiateMsg {
         admin: "admin0000".to_string(),
         denom: "uluna".to_string(),
-        vesting_periods: [
+        vesting_periods: vec![
             15552000i64,
             15552000i64,
             46656000i64,
@@ -689,6 +1614,17 @@ fn claim_terra() {
         prefix: None,
         claim_end_time: 1955870000u64,
         fee_refund: None,
+        vesting_mode: None,
+        vesting_fallback: None,
+        cliff_seconds: None,
+        clawback_funder: None,
+        extra_denoms: None,
+        token_type: None,
+        sweep_destination: None,
+        claim_start_time: None,
+        screening_contract: None,
+        gas_pool_denom: None,
+        claim_bonus: None,
     };
 
     let info = mock_info("addr0000", &[]);
@@ -699,6 +1635,7 @@ fn claim_terra() {
     let info = mock_info("admin0000", &[]);
     let msg = ExecuteMsg::RegisterMerkleRoot {
         merkle_root: "beb27623e893c39077484c0ca17e67f432cfe1fe1d7ab8b3d6d5f6f675519de8".to_string(),
+        total_amount: None,
     };
     let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
 
@@ -713,6 +1650,7 @@ fn claim_terra() {
         ],
         message: "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9".to_string(),
         signature: "".to_string(),
+        delegate_to: None,
     };
 
     let info = mock_info("terra1zdpgj8am5nqqvht927k3etljyl6a52kwqup0je", &[]);
@@ -737,10 +1675,13 @@ fn claim_terra() {
 
     assert_eq!(
         res.messages[0],
-        SubMsg::new(CosmosMsg::Stargate {
-            type_url: "/cosmos.vesting.v1beta1.MsgCreatePeriodicVestingAccount".to_string(),
-            value: Binary(bytes),
-        })
+        SubMsg::reply_always(
+            CosmosMsg::Stargate {
+                type_url: "/cosmos.vesting.v1beta1.MsgCreatePeriodicVestingAccount".to_string(),
+                value: Binary(bytes),
+            },
+            CREATE_VESTING_ACCOUNT_REPLY_ID,
+        )
     );
 
     assert_eq!(
@@ -782,12 +1723,12 @@ fn claim_terra() {
 #[cfg(feature = "terra")]
 #[test]
 fn claim_terra_with_vested() {
-    let mut deps = mock_dependencies();
+    let mut deps = mock_dependencies_with_balance(&coins(1_000_000_000, "uluna"));
 
     let msg = InstantiateMsg {
         admin: "admin0000".to_string(),
         denom: "uluna".to_string(),
-        vesting_periods: [
+        vesting_periods: vec![
             15552000i64,
             15552000i64,
             46656000i64,
@@ -798,6 +1739,17 @@ fn claim_terra_with_vested() {
         prefix: None,
         claim_end_time: 1955870000u64,
         fee_refund: None,
+        vesting_mode: None,
+        vesting_fallback: None,
+        cliff_seconds: None,
+        clawback_funder: None,
+        extra_denoms: None,
+        token_type: None,
+        sweep_destination: None,
+        claim_start_time: None,
+        screening_contract: None,
+        gas_pool_denom: None,
+        claim_bonus: None,
     };
 
     let info = mock_info("addr0000", &[]);
@@ -808,6 +1760,7 @@ fn claim_terra_with_vested() {
     let info = mock_info("admin0000", &[]);
     let msg = ExecuteMsg::RegisterMerkleRoot {
         merkle_root: "0c38144e58971ea7e80324ca7a3ad757f6f3fc1630ee31f72ce39640508c4f53".to_string(),
+        total_amount: None,
     };
     let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
 
@@ -822,6 +1775,7 @@ fn claim_terra_with_vested() {
         ],
         message: "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9".to_string(),
         signature: "".to_string(),
+        delegate_to: None,
     };
 
     let info = mock_info("terra1dcegyrekltswvyy0xy69ydgxn9x8x32zdtapd8", &[]);
@@ -853,10 +1807,13 @@ fn claim_terra_with_vested() {
     );
     assert_eq!(
         res.messages[1],
-        SubMsg::new(CosmosMsg::Stargate {
-            type_url: "/cosmos.vesting.v1beta1.MsgCreatePeriodicVestingAccount".to_string(),
-            value: Binary(bytes),
-        })
+        SubMsg::reply_always(
+            CosmosMsg::Stargate {
+                type_url: "/cosmos.vesting.v1beta1.MsgCreatePeriodicVestingAccount".to_string(),
+                value: Binary(bytes),
+            },
+            CREATE_VESTING_ACCOUNT_REPLY_ID,
+        )
     );
 
     assert_eq!(
@@ -903,7 +1860,7 @@ fn claim_terra_fail() {
     let msg = InstantiateMsg {
         admin: "admin0000".to_string(),
         denom: "uluna".to_string(),
-        vesting_periods: [
+        vesting_periods: vec![
             15552000i64,
             15552000i64,
             46656000i64,
@@ -914,6 +1871,17 @@ fn claim_terra_fail() {
         prefix: None,
         claim_end_time: 1955870000u64,
         fee_refund: None,
+        vesting_mode: None,
+        vesting_fallback: None,
+        cliff_seconds: None,
+        clawback_funder: None,
+        extra_denoms: None,
+        token_type: None,
+        sweep_destination: None,
+        claim_start_time: None,
+        screening_contract: None,
+        gas_pool_denom: None,
+        claim_bonus: None,
     };
 
     let info = mock_info("addr0000", &[]);
@@ -924,6 +1892,7 @@ fn claim_terra_fail() {
     let info = mock_info("admin0000", &[]);
     let msg = ExecuteMsg::RegisterMerkleRoot {
         merkle_root: "41be415f546ffcd24173c6c435bd6f37942b654365454b6d554a32b71c7d3eb3".to_string(),
+        total_amount: None,
     };
     let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
 
@@ -937,6 +1906,7 @@ fn claim_terra_fail() {
         ],
         message: "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9".to_string(),
         signature: "".to_string(),
+        delegate_to: None,
     };
 
     let info = mock_info("terra1dcegyrekltswvyy0xy69ydgxn9x8x32zdtaps8", &[]);
@@ -956,6 +1926,7 @@ fn claim_terra_fail() {
         ],
         message: "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9".to_string(),
         signature: "".to_string(),
+        delegate_to: None,
     };
 
     let mut env = mock_env();
@@ -976,7 +1947,7 @@ fn end_airdrop() {
     let msg = InstantiateMsg {
         admin: "admin0000".to_string(),
         denom: "uluna".to_string(),
-        vesting_periods: [
+        vesting_periods: vec![
             15552000i64,
             15552000i64,
             46656000i64,
@@ -987,6 +1958,17 @@ fn end_airdrop() {
         prefix: None,
         claim_end_time: 1655870000u64,
         fee_refund: None,
+        vesting_mode: None,
+        vesting_fallback: None,
+        cliff_seconds: None,
+        clawback_funder: None,
+        extra_denoms: None,
+        token_type: None,
+        sweep_destination: None,
+        claim_start_time: None,
+        screening_contract: None,
+        gas_pool_denom: None,
+        claim_bonus: None,
     };
 
     let info = mock_info("addr0000", &[]);
@@ -1041,3 +2023,3070 @@ fn end_airdrop() {
         })
     );
 }
+
+#[test]
+fn end_airdrop_fully_reserved() {
+    let mut deps = mock_dependencies_with_balance(&coins(1000, "uluna"));
+
+    let msg = InstantiateMsg {
+        admin: "admin0000".to_string(),
+        denom: "uluna".to_string(),
+        vesting_periods: vec![15552000i64],
+        start_time: None,
+        prefix: None,
+        claim_end_time: 1655870000u64,
+        fee_refund: None,
+        vesting_mode: Some(VestingMode::Escrow),
+        vesting_fallback: None,
+        cliff_seconds: None,
+        clawback_funder: None,
+        extra_denoms: None,
+        token_type: None,
+        sweep_destination: None,
+        claim_start_time: None,
+        screening_contract: None,
+        gas_pool_denom: None,
+        claim_bonus: None,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // Everything was claimed into escrow but not withdrawn yet
+    ESCROWED
+        .save(deps.as_mut().storage, &Uint128::new(1000))
+        .unwrap();
+
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(1655900000u64);
+    let res = execute(
+        deps.as_mut(),
+        env,
+        mock_info("admin0000", &[]),
+        ExecuteMsg::End {},
+    )
+    .unwrap();
+    assert!(res.messages.is_empty());
+}
+
+#[cfg(feature = "terra")]
+#[test]
+fn claim_terra_monthly_tranches() {
+    let mut deps = mock_dependencies_with_balance(&coins(1_000_000_000, "uluna"));
+
+    let msg = InstantiateMsg {
+        admin: "admin0000".to_string(),
+        denom: "uluna".to_string(),
+        vesting_periods: vec![2592000i64; 12],
+        start_time: Some(1655360550i64),
+        prefix: None,
+        claim_end_time: 1955870000u64,
+        fee_refund: None,
+        vesting_mode: None,
+        vesting_fallback: None,
+        cliff_seconds: None,
+        clawback_funder: None,
+        extra_denoms: None,
+        token_type: None,
+        sweep_destination: None,
+        claim_start_time: None,
+        screening_contract: None,
+        gas_pool_denom: None,
+        claim_bonus: None,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let env = mock_env();
+    let _res = instantiate(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    let allocation =
+        "terra1dcegyrekltswvyy0xy69ydgxn9x8x32zdtapd8,0,1,2,3,4,5,6,7,8,9,10,11,12".to_string();
+    let info = mock_info("admin0000", &[]);
+    let msg = ExecuteMsg::RegisterMerkleRoot {
+        merkle_root: single_leaf_root(&allocation),
+        total_amount: None,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // a leaf with fewer amounts than configured periods is rejected
+    let msg = ExecuteMsg::Claim {
+        allocation: "terra1dcegyrekltswvyy0xy69ydgxn9x8x32zdtapd8,0,1,2,3,4,5".to_string(),
+        proofs: vec![],
+        message: "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9".to_string(),
+        signature: "".to_string(),
+        delegate_to: None,
+    };
+    let info = mock_info("terra1dcegyrekltswvyy0xy69ydgxn9x8x32zdtapd8", &[]);
+    assert_eq!(
+        execute(deps.as_mut(), mock_env(), info.clone(), msg),
+        Err(StdError::generic_err("unable to parse claim amount 6"))
+    );
+
+    // as is a leaf with more amounts than configured periods
+    let msg = ExecuteMsg::Claim {
+        allocation: format!("{},13", allocation),
+        proofs: vec![],
+        message: "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9".to_string(),
+        signature: "".to_string(),
+        delegate_to: None,
+    };
+    assert_eq!(
+        execute(deps.as_mut(), mock_env(), info.clone(), msg),
+        Err(StdError::generic_err("too many claim amounts, expected 12"))
+    );
+
+    let msg = ExecuteMsg::Claim {
+        allocation,
+        proofs: vec![],
+        message: "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9".to_string(),
+        signature: "".to_string(),
+        delegate_to: None,
+    };
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let mut vesting_msg = MsgCreatePeriodicVestingAccount::new();
+    vesting_msg.from_address = env.contract.address.to_string();
+    vesting_msg.to_address = "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9".to_string();
+    vesting_msg.start_time = 1655360550i64;
+    vesting_msg.vesting_periods = (1..=12)
+        .map(|i| to_period(&(2592000i64, i.to_string())))
+        .collect::<Vec<Period>>();
+
+    let bytes = Message::write_to_bytes(&vesting_msg).unwrap();
+
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::reply_always(
+            CosmosMsg::Stargate {
+                type_url: "/cosmos.vesting.v1beta1.MsgCreatePeriodicVestingAccount".to_string(),
+                value: Binary(bytes),
+            },
+            CREATE_VESTING_ACCOUNT_REPLY_ID,
+        )]
+    );
+    assert_eq!(res.attributes[4], attr("vesting", "78"));
+}
+
+#[cfg(feature = "terra")]
+#[test]
+fn claim_terra_continuous_and_delayed() {
+    // Continuous accounts can't start later than the claim
+    for (vesting_mode, start_time) in [
+        (VestingMode::Continuous, None),
+        (VestingMode::Delayed, Some(1655360550i64)),
+    ] {
+        let mut deps = mock_dependencies_with_balance(&coins(1_000_000_000, "uluna"));
+
+        let msg = InstantiateMsg {
+            admin: "admin0000".to_string(),
+            denom: "uluna".to_string(),
+            vesting_periods: vec![15552000i64, 15552000i64, 46656000i64],
+            start_time,
+            prefix: None,
+            claim_end_time: 1955870000u64,
+            fee_refund: None,
+            vesting_mode: Some(vesting_mode.clone()),
+            vesting_fallback: None,
+            cliff_seconds: None,
+            clawback_funder: None,
+            extra_denoms: None,
+            token_type: None,
+            sweep_destination: None,
+            claim_start_time: None,
+            screening_contract: None,
+            gas_pool_denom: None,
+            claim_bonus: None,
+        };
+
+        let info = mock_info("addr0000", &[]);
+        let env = mock_env();
+        let _res = instantiate(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+        let config: ConfigResponse = from_binary(&res).unwrap();
+        assert_eq!(config.vesting_mode, vesting_mode);
+
+        let allocation = "terra1dcegyrekltswvyy0xy69ydgxn9x8x32zdtapd8,100,1000,0,2000".to_string();
+        let info = mock_info("admin0000", &[]);
+        let msg = ExecuteMsg::RegisterMerkleRoot {
+            merkle_root: single_leaf_root(&allocation),
+            total_amount: None,
+        };
+        let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let msg = ExecuteMsg::Claim {
+            allocation,
+            proofs: vec![],
+            message: "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9".to_string(),
+            signature: "".to_string(),
+            delegate_to: None,
+        };
+        let info = mock_info("terra1dcegyrekltswvyy0xy69ydgxn9x8x32zdtapd8", &[]);
+        let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let mut coin = VestingCoin::new();
+        coin.denom = "uluna".to_string();
+        coin.amount = "3000".to_string();
+        let mut vesting_msg = MsgCreateVestingAccount::new();
+        vesting_msg.from_address = env.contract.address.to_string();
+        vesting_msg.to_address = "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9".to_string();
+        vesting_msg.amount = vec![coin];
+        vesting_msg.end_time = start_time.unwrap_or(env.block.time.seconds() as i64) + 77760000i64;
+        vesting_msg.delayed = vesting_mode == VestingMode::Delayed;
+
+        let bytes = Message::write_to_bytes(&vesting_msg).unwrap();
+
+        assert_eq!(
+            res.messages,
+            vec![
+                SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                    to_address: "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9".to_string(),
+                    amount: coins(100, "uluna")
+                })),
+                SubMsg::reply_always(
+                    CosmosMsg::Stargate {
+                        type_url: "/cosmos.vesting.v1beta1.MsgCreateVestingAccount".to_string(),
+                        value: Binary(bytes),
+                    },
+                    CREATE_VESTING_ACCOUNT_REPLY_ID,
+                )
+            ]
+        );
+    }
+}
+
+#[test]
+fn continuous_vesting_restrictions() {
+    let mut deps = mock_dependencies();
+
+    let mut msg = InstantiateMsg {
+        admin: "admin0000".to_string(),
+        denom: "uluna".to_string(),
+        vesting_periods: vec![15552000i64, 15552000i64],
+        start_time: None,
+        prefix: None,
+        claim_end_time: 1955870000u64,
+        fee_refund: None,
+        vesting_mode: Some(VestingMode::Continuous),
+        vesting_fallback: None,
+        cliff_seconds: Some(15552000u64),
+        clawback_funder: None,
+        extra_denoms: None,
+        token_type: None,
+        sweep_destination: None,
+        claim_start_time: None,
+        screening_contract: None,
+        gas_pool_denom: None,
+        claim_bonus: None,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    assert_eq!(
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg.clone()),
+        Err(StdError::generic_err(
+            "continuous vesting does not support a cliff"
+        ))
+    );
+
+    msg.cliff_seconds = None;
+    msg.start_time = Some(mock_env().block.time.seconds() as i64 + 1);
+    assert_eq!(
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg.clone()),
+        Err(StdError::generic_err(
+            "continuous vesting cannot start in the future"
+        ))
+    );
+
+    msg.start_time = Some(mock_env().block.time.seconds() as i64);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let msg = ExecuteMsg::RegisterSchedule {
+        id: "cliff".to_string(),
+        periods: vec![15552000i64],
+        cliff_seconds: Some(15552000u64),
+    };
+    assert_eq!(
+        execute(deps.as_mut(), mock_env(), mock_info("admin0000", &[]), msg),
+        Err(StdError::generic_err(
+            "continuous vesting does not support a cliff"
+        ))
+    );
+}
+
+#[cfg(feature = "terra")]
+#[test]
+fn claim_terra_multi_denom() {
+    let mut deps = mock_dependencies_with_balance(&[
+        Coin::new(5000u128, "uluna"),
+        Coin::new(300u128, "uusdc"),
+    ]);
+
+    let mut msg = InstantiateMsg {
+        admin: "admin0000".to_string(),
+        denom: "uluna".to_string(),
+        vesting_periods: vec![1000i64, 1000i64],
+        start_time: Some(1571797419i64),
+        prefix: None,
+        claim_end_time: 1571800000u64,
+        fee_refund: Some(Uint128::new(10)),
+        vesting_mode: None,
+        vesting_fallback: None,
+        cliff_seconds: None,
+        clawback_funder: None,
+        extra_denoms: Some(vec!["uusdc".to_string()]),
+        token_type: None,
+        sweep_destination: None,
+        claim_start_time: None,
+        screening_contract: None,
+        gas_pool_denom: None,
+        claim_bonus: None,
+    };
+
+    // escrow can't hold the extra denoms
+    let info = mock_info("addr0000", &[]);
+    assert_eq!(
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg.clone()),
+        Err(StdError::generic_err(
+            "escrow vesting supports a single denom"
+        ))
+    );
+
+    msg.vesting_fallback = Some(VestingFallback::Liquid);
+    msg.extra_denoms = Some(vec!["uusdc".to_string(), "uluna".to_string()]);
+    assert_eq!(
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg.clone()),
+        Err(StdError::generic_err("duplicate denom: uluna"))
+    );
+
+    msg.extra_denoms = Some(vec!["uusdc".to_string()]);
+    let env = mock_env();
+    let _res = instantiate(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    let allocation = "terra1dcegyrekltswvyy0xy69ydgxn9x8x32zdtapd8,100|50,1000|0,0|20".to_string();
+    let info = mock_info("admin0000", &[]);
+    let msg = ExecuteMsg::RegisterMerkleRoot {
+        merkle_root: single_leaf_root(&allocation),
+        total_amount: None,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let msg = ExecuteMsg::Claim {
+        allocation,
+        proofs: vec![],
+        message: "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9".to_string(),
+        signature: "".to_string(),
+        delegate_to: None,
+    };
+    let info = mock_info("terra1dcegyrekltswvyy0xy69ydgxn9x8x32zdtapd8", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let mut luna = VestingCoin::new();
+    luna.denom = "uluna".to_string();
+    luna.amount = "1000".to_string();
+    let mut period1 = Period::new();
+    period1.length = 1000i64;
+    period1.amount = vec![luna];
+    let mut usdc = VestingCoin::new();
+    usdc.denom = "uusdc".to_string();
+    usdc.amount = "20".to_string();
+    let mut period2 = Period::new();
+    period2.length = 1000i64;
+    period2.amount = vec![usdc];
+    let mut vesting_msg = MsgCreatePeriodicVestingAccount::new();
+    vesting_msg.from_address = env.contract.address.to_string();
+    vesting_msg.to_address = "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9".to_string();
+    vesting_msg.start_time = 1571797419i64;
+    vesting_msg.vesting_periods = vec![period1, period2];
+
+    // the fee refund is taken from the main denom only
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9".to_string(),
+                amount: vec![Coin::new(90u128, "uluna"), Coin::new(50u128, "uusdc")]
+            })),
+            SubMsg::reply_always(
+                CosmosMsg::Stargate {
+                    type_url: "/cosmos.vesting.v1beta1.MsgCreatePeriodicVestingAccount".to_string(),
+                    value: Binary(Message::write_to_bytes(&vesting_msg).unwrap()),
+                },
+                CREATE_VESTING_ACCOUNT_REPLY_ID,
+            ),
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "terra1dcegyrekltswvyy0xy69ydgxn9x8x32zdtapd8".to_string(),
+                amount: coins(10, "uluna")
+            })),
+        ]
+    );
+
+    // the liquid fallback pays out every denom
+    let res = reply(
+        deps.as_mut(),
+        mock_env(),
+        Reply {
+            id: CREATE_VESTING_ACCOUNT_REPLY_ID,
+            result: SubMsgResult::Err("account already exists".to_string()),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9".to_string(),
+            amount: vec![Coin::new(1000u128, "uluna"), Coin::new(20u128, "uusdc")]
+        }))]
+    );
+
+    let allocation = "terra1qfqa2eu9wp272ha93lj4yhcenrc6ymng079nu8,100|50|20".to_string();
+    let info = mock_info("admin0000", &[]);
+    let msg = ExecuteMsg::RegisterMerkleRoot {
+        merkle_root: single_leaf_root(&allocation),
+        total_amount: None,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    let msg = ExecuteMsg::Claim {
+        allocation,
+        proofs: vec![],
+        message: "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9".to_string(),
+        signature: "".to_string(),
+        delegate_to: None,
+    };
+    let info = mock_info("terra1qfqa2eu9wp272ha93lj4yhcenrc6ymng079nu8", &[]);
+    assert_eq!(
+        execute(deps.as_mut(), mock_env(), info, msg),
+        Err(StdError::generic_err(
+            "too many denoms in claim amount, expected 2"
+        ))
+    );
+
+    // the end sweep returns every denom
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(1571800001u64);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("admin0000", &[]),
+        ExecuteMsg::End {},
+    )
+    .unwrap();
+    let mut msg = MsgFundCommunityPool::new();
+    let mut luna = DistributionCoin::new();
+    luna.amount = "5000".to_string();
+    luna.denom = "uluna".to_string();
+    let mut usdc = DistributionCoin::new();
+    usdc.amount = "300".to_string();
+    usdc.denom = "uusdc".to_string();
+    msg.amount = vec![luna, usdc];
+    msg.depositor = env.contract.address.to_string();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Stargate {
+            type_url: "/cosmos.distribution.v1beta1.MsgFundCommunityPool".to_string(),
+            value: Binary(Message::write_to_bytes(&msg).unwrap()),
+        })]
+    );
+}
+
+#[cfg(feature = "terra")]
+#[test]
+fn claim_terra_cw20() {
+    let mut deps = mock_dependencies();
+    deps.querier.update_wasm(|query| match query {
+        WasmQuery::Smart { contract_addr, .. } if contract_addr == "token0000" => {
+            SystemResult::Ok(ContractResult::Ok(
+                to_binary(&BalanceResponse {
+                    balance: Uint128::new(5000),
+                })
+                .unwrap(),
+            ))
+        }
+        _ => panic!("unexpected query"),
+    });
+
+    let mut msg = InstantiateMsg {
+        admin: "admin0000".to_string(),
+        denom: "token0000".to_string(),
+        vesting_periods: vec![1000i64, 1000i64],
+        start_time: Some(1571797419i64),
+        prefix: None,
+        claim_end_time: 1571800000u64,
+        fee_refund: Some(Uint128::new(10)),
+        vesting_mode: Some(VestingMode::Periodic),
+        vesting_fallback: None,
+        cliff_seconds: None,
+        clawback_funder: None,
+        extra_denoms: None,
+        token_type: Some(TokenType::Cw20),
+        sweep_destination: None,
+        claim_start_time: None,
+        screening_contract: None,
+        gas_pool_denom: None,
+        claim_bonus: None,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    assert_eq!(
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg.clone()),
+        Err(StdError::generic_err(
+            "cw20 campaigns must vest through escrow"
+        ))
+    );
+
+    msg.vesting_mode = None;
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(config.vesting_mode, VestingMode::Escrow);
+    assert_eq!(config.token_type, TokenType::Cw20);
+    assert_eq!(config.sweep_destination, SweepDestination::Admin);
+
+    // only the campaign token can fund the contract
+    let fund = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "admin0000".to_string(),
+        amount: Uint128::new(5000),
+        msg: to_binary(&Cw20HookMsg::Fund {}).unwrap(),
+    });
+    assert_eq!(
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("token0001", &[]),
+            fund.clone()
+        ),
+        Err(StdError::generic_err("unauthorized token"))
+    );
+    let res = execute(deps.as_mut(), mock_env(), mock_info("token0000", &[]), fund).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "fund"),
+            attr("sender", "admin0000"),
+            attr("amount", "5000"),
+        ]
+    );
+
+    let allocation = "terra1dcegyrekltswvyy0xy69ydgxn9x8x32zdtapd8,100,1000,2000".to_string();
+    let info = mock_info("admin0000", &[]);
+    let msg = ExecuteMsg::RegisterMerkleRoot {
+        merkle_root: single_leaf_root(&allocation),
+        total_amount: None,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let msg = ExecuteMsg::Claim {
+        allocation,
+        proofs: vec![],
+        message: "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9".to_string(),
+        signature: "".to_string(),
+        delegate_to: None,
+    };
+    let info = mock_info("terra1dcegyrekltswvyy0xy69ydgxn9x8x32zdtapd8", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let transfer = |recipient: &str, amount: u128| -> CosmosMsg {
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "token0000".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount: Uint128::new(amount),
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    };
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(transfer("terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9", 90)),
+            SubMsg::new(transfer("terra1dcegyrekltswvyy0xy69ydgxn9x8x32zdtapd8", 10)),
+        ]
+    );
+    assert_eq!(res.attributes[4], attr("vesting", "3000"));
+
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(1571798419u64);
+    let res = execute(
+        deps.as_mut(),
+        env,
+        mock_info("terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9", &[]),
+        ExecuteMsg::Withdraw {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(transfer(
+            "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9",
+            1000
+        ))]
+    );
+
+    let msg = ExecuteMsg::ProposeAdmin {
+        admin: "admin0001".to_string(),
+        expiry: None,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("admin0000", &[]), msg).unwrap();
+    let msg = ExecuteMsg::AcceptAdmin {};
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("admin0001", &[]), msg).unwrap();
+
+    // the leftover minus the remaining escrow goes to the current admin
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(1571800001u64);
+    let res = execute(
+        deps.as_mut(),
+        env,
+        mock_info("admin0001", &[]),
+        ExecuteMsg::End {},
+    )
+    .unwrap();
+    assert_eq!(res.messages, vec![SubMsg::new(transfer("admin0001", 3000))]);
+}
+
+#[cfg(feature = "terra")]
+#[test]
+fn claim_terra_delegate() {
+    let mut deps = mock_dependencies_with_balance(&coins(1_000_000_000, "uluna"));
+
+    let msg = InstantiateMsg {
+        admin: "admin0000".to_string(),
+        denom: "uluna".to_string(),
+        vesting_periods: vec![],
+        start_time: None,
+        prefix: None,
+        claim_end_time: 1955870000u64,
+        fee_refund: None,
+        vesting_mode: None,
+        vesting_fallback: None,
+        cliff_seconds: None,
+        clawback_funder: None,
+        extra_denoms: None,
+        token_type: None,
+        sweep_destination: None,
+        claim_start_time: None,
+        screening_contract: None,
+        gas_pool_denom: None,
+        claim_bonus: None,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let env = mock_env();
+    let _res = instantiate(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    let msg = ExecuteMsg::UpdateValidators {
+        add: vec![
+            "terravaloper1abc".to_string(),
+            "terravaloper1def".to_string(),
+        ],
+        remove: vec![],
+    };
+    assert_eq!(
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("addr0000", &[]),
+            msg.clone()
+        ),
+        Err(StdError::generic_err("unauthorized"))
+    );
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("admin0000", &[]), msg).unwrap();
+    let msg = ExecuteMsg::UpdateValidators {
+        add: vec![],
+        remove: vec!["terravaloper1def".to_string()],
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("admin0000", &[]), msg).unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Validators {}).unwrap();
+    let validators: ValidatorsResponse = from_binary(&res).unwrap();
+    assert_eq!(validators.validators, vec!["terravaloper1abc".to_string()]);
+
+    let allocation = "terra1dcegyrekltswvyy0xy69ydgxn9x8x32zdtapd8,100".to_string();
+    let info = mock_info("admin0000", &[]);
+    let msg = ExecuteMsg::RegisterMerkleRoot {
+        merkle_root: single_leaf_root(&allocation),
+        total_amount: None,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let claim = |delegate_to: &str| ExecuteMsg::Claim {
+        allocation: allocation.clone(),
+        proofs: vec![],
+        message: "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9".to_string(),
+        signature: "".to_string(),
+        delegate_to: Some(delegate_to.to_string()),
+    };
+    let info = mock_info("terra1dcegyrekltswvyy0xy69ydgxn9x8x32zdtapd8", &[]);
+    assert_eq!(
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            claim("terravaloper1def")
+        ),
+        Err(StdError::generic_err("validator not allowed"))
+    );
+    let res = execute(deps.as_mut(), mock_env(), info, claim("terravaloper1abc")).unwrap();
+
+    let mut coin = StakingCoin::new();
+    coin.denom = "uluna".to_string();
+    coin.amount = "100".to_string();
+    let mut delegate = MsgDelegate::new();
+    delegate.delegator_address = "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9".to_string();
+    delegate.validator_address = "terravaloper1abc".to_string();
+    delegate.amount = Some(coin).into();
+    let mut any = Any::new();
+    any.type_url = "/cosmos.staking.v1beta1.MsgDelegate".to_string();
+    any.value = Message::write_to_bytes(&delegate).unwrap();
+    let mut exec = MsgExec::new();
+    exec.grantee = env.contract.address.to_string();
+    exec.msgs = vec![any];
+
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9".to_string(),
+                amount: coins(100, "uluna")
+            })),
+            SubMsg::reply_on_error(
+                CosmosMsg::Stargate {
+                    type_url: "/cosmos.authz.v1beta1.MsgExec".to_string(),
+                    value: Binary(Message::write_to_bytes(&exec).unwrap()),
+                },
+                DELEGATE_REPLY_ID,
+            ),
+        ]
+    );
+    assert_eq!(res.attributes[5], attr("delegate_to", "terravaloper1abc"));
+
+    // without an authz grant the tokens stay liquid
+    let res = reply(
+        deps.as_mut(),
+        mock_env(),
+        Reply {
+            id: DELEGATE_REPLY_ID,
+            result: SubMsgResult::Err("authorization not found".to_string()),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.events,
+        vec![Event::new("delegation_failed").add_attribute("reason", "authorization not found")]
+    );
+}
+
+#[cfg(feature = "terra")]
+#[test]
+fn claim_terra_blocked() {
+    let mut deps = mock_dependencies_with_balance(&coins(1_000_000_000, "uluna"));
+    deps.querier.update_wasm(|query| match query {
+        WasmQuery::Smart { contract_addr, msg } if contract_addr == "screening0000" => {
+            let ScreeningQueryMsg::IsBlocked { address } = from_binary(msg).unwrap();
+            SystemResult::Ok(ContractResult::Ok(
+                to_binary(&ScreeningResponse {
+                    blocked: address == "terra1dcegyrekltswvyy0xy69ydgxn9x8x32zdtapd8",
+                })
+                .unwrap(),
+            ))
+        }
+        _ => panic!("unexpected query"),
+    });
+
+    let msg = InstantiateMsg {
+        admin: "admin0000".to_string(),
+        denom: "uluna".to_string(),
+        vesting_periods: vec![],
+        start_time: None,
+        prefix: None,
+        claim_end_time: 1955870000u64,
+        fee_refund: None,
+        vesting_mode: None,
+        vesting_fallback: None,
+        cliff_seconds: None,
+        clawback_funder: None,
+        extra_denoms: None,
+        token_type: None,
+        sweep_destination: None,
+        claim_start_time: None,
+        screening_contract: None,
+        gas_pool_denom: None,
+        claim_bonus: None,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let msg = ExecuteMsg::UpdateBlocklist {
+        add: vec!["TERRA1JH4TH9U5ZK4WA38WGTMXJMPSVWNSJEVJQAZ8H9".to_string()],
+        remove: vec![],
+    };
+    assert_eq!(
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("addr0000", &[]),
+            msg.clone()
+        ),
+        Err(StdError::generic_err("unauthorized"))
+    );
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("admin0000", &[]), msg).unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Blocklist {}).unwrap();
+    let blocklist: BlocklistResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        blocklist.addresses,
+        vec!["terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9".to_string()]
+    );
+
+    let allocation = "terra1dcegyrekltswvyy0xy69ydgxn9x8x32zdtapd8,100".to_string();
+    let msg = ExecuteMsg::RegisterMerkleRoot {
+        merkle_root: single_leaf_root(&allocation),
+        total_amount: None,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("admin0000", &[]), msg).unwrap();
+
+    let claim = ExecuteMsg::Claim {
+        allocation: allocation.clone(),
+        proofs: vec![],
+        message: "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9".to_string(),
+        signature: "".to_string(),
+        delegate_to: None,
+    };
+    let simulate = QueryMsg::SimulateClaim {
+        allocation,
+        proofs: vec![],
+        message: "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9".to_string(),
+        signature: "".to_string(),
+        sender: "terra1dcegyrekltswvyy0xy69ydgxn9x8x32zdtapd8".to_string(),
+    };
+    let info = mock_info("terra1dcegyrekltswvyy0xy69ydgxn9x8x32zdtapd8", &[]);
+
+    // a blocked recipient gets nothing and can't claim as a side effect
+    assert_eq!(
+        execute(deps.as_mut(), mock_env(), info.clone(), claim.clone()),
+        Err(StdError::generic_err(
+            "address blocked by blocklist: terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9"
+        ))
+    );
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::IsClaimed {
+            address: "terra1dcegyrekltswvyy0xy69ydgxn9x8x32zdtapd8".to_string(),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        from_binary::<IsClaimedResponse>(&res).unwrap(),
+        IsClaimedResponse { is_claimed: false }
+    );
+    let res = query(deps.as_ref(), mock_env(), simulate.clone()).unwrap();
+    let simulation: SimulateClaimResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        simulation.error,
+        Some(
+            "address blocked by blocklist: terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9"
+                .to_string()
+        )
+    );
+
+    // the screening contract flags the signer
+    let msg = ExecuteMsg::UpdateBlocklist {
+        add: vec![],
+        remove: vec!["terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9".to_string()],
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("admin0000", &[]), msg).unwrap();
+    let msg = ExecuteMsg::UpdateScreeningContract {
+        address: Some("screening0000".to_string()),
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("admin0000", &[]), msg).unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), simulate).unwrap();
+    let simulation: SimulateClaimResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        simulation.error,
+        Some(
+            "address blocked by screening: terra1dcegyrekltswvyy0xy69ydgxn9x8x32zdtapd8"
+                .to_string()
+        )
+    );
+    assert_eq!(
+        execute(deps.as_mut(), mock_env(), info.clone(), claim.clone()),
+        Err(StdError::generic_err(
+            "address blocked by screening: terra1dcegyrekltswvyy0xy69ydgxn9x8x32zdtapd8"
+        ))
+    );
+
+    let msg = ExecuteMsg::UpdateScreeningContract { address: None };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("admin0000", &[]), msg).unwrap();
+    let res = execute(deps.as_mut(), mock_env(), info, claim).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9".to_string(),
+            amount: coins(100, "uluna")
+        }))]
+    );
+}
+
+#[cfg(feature = "terra")]
+#[test]
+fn claim_terra_solvency() {
+    let mut deps = mock_dependencies_with_balance(&coins(80, "uluna"));
+
+    let msg = InstantiateMsg {
+        admin: "admin0000".to_string(),
+        denom: "uluna".to_string(),
+        vesting_periods: vec![],
+        start_time: None,
+        prefix: None,
+        claim_end_time: 1955870000u64,
+        fee_refund: None,
+        vesting_mode: None,
+        vesting_fallback: None,
+        cliff_seconds: None,
+        clawback_funder: None,
+        extra_denoms: None,
+        token_type: None,
+        sweep_destination: None,
+        claim_start_time: None,
+        screening_contract: None,
+        gas_pool_denom: None,
+        claim_bonus: None,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let env = mock_env();
+    let _res = instantiate(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    let allocation = "terra1dcegyrekltswvyy0xy69ydgxn9x8x32zdtapd8,100".to_string();
+    let register = |total_amount: u128| ExecuteMsg::RegisterMerkleRoot {
+        merkle_root: single_leaf_root(&allocation),
+        total_amount: Some(Uint128::new(total_amount)),
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin0000", &[]),
+        register(50),
+    )
+    .unwrap();
+    assert_eq!(res.attributes[2], attr("total_amount", "50"));
+
+    let claim = ExecuteMsg::Claim {
+        allocation: allocation.clone(),
+        proofs: vec![],
+        message: "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9".to_string(),
+        signature: "".to_string(),
+        delegate_to: None,
+    };
+    let info = mock_info("terra1dcegyrekltswvyy0xy69ydgxn9x8x32zdtapd8", &[]);
+    assert_eq!(
+        execute(deps.as_mut(), mock_env(), info.clone(), claim.clone()),
+        Err(StdError::generic_err(
+            "claim exceeds the registered total_amount"
+        ))
+    );
+
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin0000", &[]),
+        register(300),
+    )
+    .unwrap();
+    assert_eq!(
+        execute(deps.as_mut(), mock_env(), info.clone(), claim.clone()),
+        Err(StdError::generic_err(
+            "insufficient contract balance: need 100uluna, have 80uluna"
+        ))
+    );
+
+    deps.querier
+        .update_balance(env.contract.address.clone(), coins(150, "uluna"));
+    let _res = execute(deps.as_mut(), mock_env(), info, claim).unwrap();
+    deps.querier
+        .update_balance(env.contract.address, coins(50, "uluna"));
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Solvency {}).unwrap();
+    assert_eq!(
+        from_binary::<SolvencyResponse>(&res).unwrap(),
+        SolvencyResponse {
+            denom: "uluna".to_string(),
+            balance: Uint128::new(50),
+            total_amount: Some(Uint128::new(300)),
+            claimed: Uint128::new(100),
+            refunded: Uint128::zero(),
+            escrowed: Uint128::zero(),
+            gas_pool: Uint128::zero(),
+            bonus_pool: Uint128::zero(),
+            liability: Uint128::new(200),
+            surplus: Uint128::zero(),
+            shortfall: Uint128::new(150),
+        }
+    );
+
+    assert_eq!(
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin0000", &[]),
+            register(99)
+        ),
+        Err(StdError::generic_err(
+            "total_amount is below the amount already claimed"
+        ))
+    );
+}
+
+#[cfg(feature = "terra")]
+#[test]
+fn claim_terra_bonus() {
+    let mut deps = mock_dependencies_with_balance(&coins(1000, "uluna"));
+
+    let start_time = mock_env().block.time.seconds();
+    let mut msg = InstantiateMsg {
+        admin: "admin0000".to_string(),
+        denom: "uluna".to_string(),
+        vesting_periods: vec![1000i64],
+        start_time: None,
+        prefix: None,
+        claim_end_time: 1955870000u64,
+        fee_refund: None,
+        vesting_mode: None,
+        vesting_fallback: None,
+        cliff_seconds: None,
+        clawback_funder: None,
+        extra_denoms: None,
+        token_type: None,
+        sweep_destination: None,
+        claim_start_time: None,
+        screening_contract: None,
+        gas_pool_denom: None,
+        claim_bonus: Some(ClaimBonus {
+            multiplier: Decimal::percent(90),
+            start_time,
+            end_time: start_time + 1000,
+        }),
+    };
+
+    let info = mock_info("addr0000", &[]);
+    assert_eq!(
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg.clone()),
+        Err(StdError::generic_err(
+            "claim bonus multiplier must be at least 1"
+        ))
+    );
+    msg.claim_bonus = Some(ClaimBonus {
+        multiplier: Decimal::percent(150),
+        start_time,
+        end_time: start_time + 1000,
+    });
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let fund = ExecuteMsg::FundBonusPool {};
+    assert_eq!(
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("addr0000", &coins(60, "uusd")),
+            fund.clone()
+        ),
+        Err(StdError::generic_err("bonus pool only accepts uluna"))
+    );
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &coins(60, "uluna")),
+        fund,
+    )
+    .unwrap();
+    assert_eq!(res.attributes[3], attr("bonus_pool", "60"));
+
+    let claims = [
+        (
+            "terra1dcegyrekltswvyy0xy69ydgxn9x8x32zdtapd8",
+            "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9",
+            500u64,
+        ),
+        (
+            "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9",
+            "terra1dcegyrekltswvyy0xy69ydgxn9x8x32zdtapd8",
+            600u64,
+        ),
+    ];
+    let mut responses = vec![];
+    for (signer, recipient, elapsed) in claims {
+        let allocation = format!("{},100,100", signer);
+        let msg = ExecuteMsg::RegisterMerkleRoot {
+            merkle_root: single_leaf_root(&allocation),
+            total_amount: None,
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("admin0000", &[]), msg).unwrap();
+
+        let mut env = mock_env();
+        env.block.time = Timestamp::from_seconds(start_time + elapsed);
+        let msg = ExecuteMsg::Claim {
+            allocation,
+            proofs: vec![],
+            message: recipient.to_string(),
+            signature: "".to_string(),
+            delegate_to: None,
+        };
+        responses.push(execute(deps.as_mut(), env, mock_info(signer, &[]), msg).unwrap());
+    }
+
+    // Half way through the decay, a 1.5x bonus is down to 1.25x
+    for attribute in [
+        attr("vested", "125"),
+        attr("vesting", "125"),
+        attr("bonus_multiplier", "1.25"),
+        attr("bonus", "50"),
+    ] {
+        assert!(responses[0].attributes.contains(&attribute));
+    }
+    // The 1.2x bonus due is capped by the 10 left in the pool
+    for attribute in [
+        attr("vested", "105"),
+        attr("vesting", "105"),
+        attr("bonus_multiplier", "1.05"),
+        attr("bonus", "10"),
+    ] {
+        assert!(responses[1].attributes.contains(&attribute));
+    }
+
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(start_time + 1000);
+    let res = query(deps.as_ref(), env, QueryMsg::ClaimBonus {}).unwrap();
+    let claim_bonus: ClaimBonusResponse = from_binary(&res).unwrap();
+    assert_eq!(claim_bonus.multiplier, Decimal::one());
+    assert_eq!(claim_bonus.pool, Uint128::zero());
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Solvency {}).unwrap();
+    let solvency: SolvencyResponse = from_binary(&res).unwrap();
+    assert_eq!(solvency.claimed, Uint128::new(400));
+    let msg = QueryMsg::Stats {
+        start_after: None,
+        limit: None,
+    };
+    let stats: StatsResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(stats.liquid, Uint128::new(230));
+    assert_eq!(stats.vesting, Uint128::new(230));
+    assert_eq!(stats.bonus, Uint128::new(60));
+}
+
+#[test]
+fn end_airdrop_keeps_gas_pool() {
+    let mut deps =
+        mock_dependencies_with_balance(&[Coin::new(1000u128, "uluna"), Coin::new(500u128, "uusd")]);
+
+    let msg = InstantiateMsg {
+        admin: "admin0000".to_string(),
+        denom: "uluna".to_string(),
+        vesting_periods: vec![],
+        start_time: None,
+        prefix: None,
+        claim_end_time: 1655870000u64,
+        fee_refund: Some(Uint128::new(10)),
+        vesting_mode: None,
+        vesting_fallback: Some(VestingFallback::Liquid),
+        cliff_seconds: None,
+        clawback_funder: None,
+        extra_denoms: Some(vec!["uusd".to_string()]),
+        token_type: None,
+        sweep_destination: Some(SweepDestination::Burn),
+        claim_start_time: None,
+        screening_contract: None,
+        gas_pool_denom: Some("uusd".to_string()),
+        claim_bonus: None,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let fund = ExecuteMsg::FundGasPool {};
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &coins(200, "uusd")),
+        fund,
+    )
+    .unwrap();
+
+    // The gas pool shares the extra denom but is left for WithdrawGasPool
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(1655900000u64);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("admin0000", &[]),
+        ExecuteMsg::End {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Burn {
+            amount: vec![Coin::new(1000u128, "uluna"), Coin::new(300u128, "uusd")],
+        }))]
+    );
+
+    let msg = ExecuteMsg::WithdrawGasPool { amount: None };
+    let res = execute(deps.as_mut(), env, mock_info("admin0000", &[]), msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "admin0000".to_string(),
+            amount: coins(200, "uusd")
+        }))]
+    );
+}
+
+#[cfg(feature = "terra")]
+#[test]
+fn claim_terra_gas_pool() {
+    let mut deps = mock_dependencies_with_balance(&coins(200, "uluna"));
+
+    let msg = InstantiateMsg {
+        admin: "admin0000".to_string(),
+        denom: "uluna".to_string(),
+        vesting_periods: vec![],
+        start_time: None,
+        prefix: None,
+        claim_end_time: 1955870000u64,
+        fee_refund: Some(Uint128::new(10)),
+        vesting_mode: None,
+        vesting_fallback: None,
+        cliff_seconds: None,
+        clawback_funder: None,
+        extra_denoms: None,
+        token_type: None,
+        sweep_destination: None,
+        claim_start_time: None,
+        screening_contract: None,
+        gas_pool_denom: Some("uusd".to_string()),
+        claim_bonus: None,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let fund = ExecuteMsg::FundGasPool {};
+    assert_eq!(
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("addr0000", &coins(15, "uluna")),
+            fund.clone()
+        ),
+        Err(StdError::generic_err("gas pool only accepts uusd"))
+    );
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &coins(15, "uusd")),
+        fund,
+    )
+    .unwrap();
+    assert_eq!(res.attributes[3], attr("gas_pool", "15"));
+
+    let claims = [
+        (
+            "terra1dcegyrekltswvyy0xy69ydgxn9x8x32zdtapd8",
+            "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9",
+        ),
+        (
+            "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9",
+            "terra1dcegyrekltswvyy0xy69ydgxn9x8x32zdtapd8",
+        ),
+    ];
+    let mut responses = vec![];
+    for (signer, recipient) in claims {
+        let allocation = format!("{},100", signer);
+        let msg = ExecuteMsg::RegisterMerkleRoot {
+            merkle_root: single_leaf_root(&allocation),
+            total_amount: None,
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("admin0000", &[]), msg).unwrap();
+
+        let msg = ExecuteMsg::Claim {
+            allocation,
+            proofs: vec![],
+            message: recipient.to_string(),
+            signature: "".to_string(),
+            delegate_to: None,
+        };
+        responses.push(execute(deps.as_mut(), mock_env(), mock_info(signer, &[]), msg).unwrap());
+    }
+
+    // The allocation is paid in full and the refund comes from the pool
+    assert_eq!(
+        responses[0].messages,
+        vec![
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9".to_string(),
+                amount: coins(100, "uluna")
+            })),
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "terra1dcegyrekltswvyy0xy69ydgxn9x8x32zdtapd8".to_string(),
+                amount: coins(10, "uusd")
+            })),
+        ]
+    );
+    assert!(responses[0].attributes.contains(&attr("gas_refund", "10")));
+
+    // Once the pool can't cover the refund, claims go through without one
+    assert_eq!(
+        responses[1].messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "terra1dcegyrekltswvyy0xy69ydgxn9x8x32zdtapd8".to_string(),
+            amount: coins(100, "uluna")
+        }))]
+    );
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::GasPool {}).unwrap();
+    assert_eq!(
+        from_binary::<GasPoolResponse>(&res).unwrap(),
+        GasPoolResponse {
+            denom: Some("uusd".to_string()),
+            balance: Uint128::new(5),
+        }
+    );
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Stats {
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    let stats: StatsResponse = from_binary(&res).unwrap();
+    assert_eq!(stats.liquid, Uint128::new(200));
+    assert_eq!(stats.fee_refunds, Uint128::zero());
+    assert_eq!(stats.gas_refunds, Some(Coin::new(10, "uusd")));
+
+    let msg = QueryMsg::ListClaims {
+        start_after: None,
+        limit: None,
+    };
+    let res: ClaimsResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    let gas_refunds: Vec<_> = res.claims.iter().map(|c| c.gas_refund.clone()).collect();
+    assert!(gas_refunds.contains(&Some(Coin::new(10, "uusd"))));
+    assert!(gas_refunds.contains(&None));
+    assert!(res.claims.iter().all(|c| c.fee_refund.is_zero()));
+
+    let withdraw = |amount| ExecuteMsg::WithdrawGasPool { amount };
+    assert_eq!(
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("addr0000", &[]),
+            withdraw(None)
+        ),
+        Err(StdError::generic_err("unauthorized"))
+    );
+    assert_eq!(
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin0000", &[]),
+            withdraw(Some(Uint128::new(6)))
+        ),
+        Err(StdError::generic_err("amount exceeds the gas pool"))
+    );
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin0000", &[]),
+        withdraw(None),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "admin0000".to_string(),
+            amount: coins(5, "uusd")
+        }))]
+    );
+}
+
+#[cfg(feature = "terra")]
+#[test]
+fn claim_terra_stats() {
+    let mut deps = mock_dependencies_with_balance(&coins(1000, "uluna"));
+
+    let msg = InstantiateMsg {
+        admin: "admin0000".to_string(),
+        denom: "uluna".to_string(),
+        vesting_periods: vec![1000i64],
+        start_time: None,
+        prefix: None,
+        claim_end_time: 1955870000u64,
+        fee_refund: Some(Uint128::new(10)),
+        vesting_mode: None,
+        vesting_fallback: None,
+        cliff_seconds: None,
+        clawback_funder: None,
+        extra_denoms: None,
+        token_type: None,
+        sweep_destination: None,
+        claim_start_time: None,
+        screening_contract: None,
+        gas_pool_denom: None,
+        claim_bonus: None,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let claims = [
+        (
+            "terra1dcegyrekltswvyy0xy69ydgxn9x8x32zdtapd8",
+            "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9",
+            1571788800u64,
+        ),
+        (
+            "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9",
+            "terra1dcegyrekltswvyy0xy69ydgxn9x8x32zdtapd8",
+            1571875200u64,
+        ),
+    ];
+    for (signer, recipient, time) in claims {
+        let allocation = format!("{},100,50", signer);
+        let msg = ExecuteMsg::RegisterMerkleRoot {
+            merkle_root: single_leaf_root(&allocation),
+            total_amount: None,
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("admin0000", &[]), msg).unwrap();
+
+        let mut env = mock_env();
+        env.block.time = Timestamp::from_seconds(time + 60);
+        let msg = ExecuteMsg::Claim {
+            allocation,
+            proofs: vec![],
+            message: recipient.to_string(),
+            signature: "".to_string(),
+            delegate_to: None,
+        };
+        let _res = execute(deps.as_mut(), env, mock_info(signer, &[]), msg).unwrap();
+    }
+
+    let stats = |start_after, limit| -> StatsResponse {
+        let msg = QueryMsg::Stats { start_after, limit };
+        from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap()
+    };
+    assert_eq!(
+        stats(None, None),
+        StatsResponse {
+            claims: 2,
+            liquid: Uint128::new(180),
+            vesting: Uint128::new(100),
+            fee_refunds: Uint128::new(20),
+            bonus: Uint128::zero(),
+            daily: vec![
+                DailyClaimsResponse {
+                    day: 1571788800u64,
+                    claims: 1,
+                },
+                DailyClaimsResponse {
+                    day: 1571875200u64,
+                    claims: 1,
+                },
+            ],
+            gas_refunds: None,
+        }
+    );
+    assert_eq!(
+        stats(Some(1571788800u64 + 3600), Some(1)).daily,
+        vec![DailyClaimsResponse {
+            day: 1571875200u64,
+            claims: 1,
+        }]
+    );
+}
+
+#[cfg(feature = "terra")]
+#[test]
+fn claim_terra_records() {
+    let mut deps = mock_dependencies_with_balance(&coins(1000, "uluna"));
+
+    let msg = InstantiateMsg {
+        admin: "admin0000".to_string(),
+        denom: "uluna".to_string(),
+        vesting_periods: vec![1000i64],
+        start_time: None,
+        prefix: None,
+        claim_end_time: 1955870000u64,
+        fee_refund: Some(Uint128::new(10)),
+        vesting_mode: None,
+        vesting_fallback: None,
+        cliff_seconds: None,
+        clawback_funder: None,
+        extra_denoms: None,
+        token_type: None,
+        sweep_destination: None,
+        claim_start_time: None,
+        screening_contract: None,
+        gas_pool_denom: None,
+        claim_bonus: None,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let signers = [
+        "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9",
+        "terra1dcegyrekltswvyy0xy69ydgxn9x8x32zdtapd8",
+    ];
+    for signer in signers {
+        let allocation = format!("{},100,50", signer);
+        let msg = ExecuteMsg::RegisterMerkleRoot {
+            merkle_root: single_leaf_root(&allocation),
+            total_amount: None,
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("admin0000", &[]), msg).unwrap();
+
+        // both signers fund the same recipient
+        let msg = ExecuteMsg::Claim {
+            allocation,
+            proofs: vec![],
+            message: "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9".to_string(),
+            signature: "".to_string(),
+            delegate_to: None,
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info(signer, &[]), msg).unwrap();
+    }
+
+    let record = |signer: &str| ClaimRecordResponse {
+        signer: signer.to_string(),
+        recipient: "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9".to_string(),
+        liquid: coins(90, "uluna"),
+        vesting: coins(50, "uluna"),
+        fee_refund: Uint128::new(10),
+        claimed_at: mock_env().block.time.seconds(),
+        relayer: signer.to_string(),
+        gas_refund: None,
+    };
+    let list = |start_after: Option<&str>, limit| -> ClaimsResponse {
+        let msg = QueryMsg::ListClaims {
+            start_after: start_after.map(|s| s.to_string()),
+            limit,
+        };
+        from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap()
+    };
+    assert_eq!(
+        list(None, None).claims,
+        vec![record(signers[1]), record(signers[0])]
+    );
+    assert_eq!(list(None, Some(1)).claims, vec![record(signers[1])]);
+    assert_eq!(
+        list(Some(signers[1]), None).claims,
+        vec![record(signers[0])]
+    );
+
+    let msg = QueryMsg::ClaimsByRecipient {
+        recipient: "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9".to_string(),
+        start_after: None,
+        limit: None,
+    };
+    let res: ClaimsResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(res.claims, vec![record(signers[1]), record(signers[0])]);
+
+    let msg = QueryMsg::ClaimsByRecipient {
+        recipient: "terra1dcegyrekltswvyy0xy69ydgxn9x8x32zdtapd8".to_string(),
+        start_after: None,
+        limit: None,
+    };
+    let res: ClaimsResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(res.claims, vec![]);
+
+    let msg = QueryMsg::AreClaimed {
+        // queried the same way claims are keyed
+        addresses: vec![
+            signers[0].to_uppercase(),
+            "terra1352nvukmex9ax4c329cp7ln9jfgjf0z7whvfca".to_string(),
+            "terra1unclaimed".to_string(),
+        ],
+    };
+    let res: AreClaimedResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(
+        res.claims,
+        vec![
+            ClaimStatusResponse {
+                address: signers[0].to_uppercase(),
+                is_claimed: true,
+            },
+            ClaimStatusResponse {
+                address: "terra1352nvukmex9ax4c329cp7ln9jfgjf0z7whvfca".to_string(),
+                is_claimed: false,
+            },
+            ClaimStatusResponse {
+                address: "terra1unclaimed".to_string(),
+                is_claimed: false,
+            },
+        ]
+    );
+
+    let msg = QueryMsg::AreClaimed {
+        addresses: vec![signers[0].to_string(); 101],
+    };
+    assert_eq!(
+        query(deps.as_ref(), mock_env(), msg),
+        Err(StdError::generic_err(
+            "too many addresses, at most 100 per query"
+        ))
+    );
+}
+
+#[test]
+fn end_sweep_destination() {
+    let mut deps = mock_dependencies_with_balance(&[Coin::new(1000u128, "uluna")]);
+
+    let mut msg = InstantiateMsg {
+        admin: "admin0000".to_string(),
+        denom: "token0000".to_string(),
+        vesting_periods: vec![],
+        start_time: None,
+        prefix: None,
+        claim_end_time: 1571800000u64,
+        fee_refund: None,
+        vesting_mode: None,
+        vesting_fallback: None,
+        cliff_seconds: None,
+        clawback_funder: None,
+        extra_denoms: None,
+        token_type: Some(TokenType::Cw20),
+        sweep_destination: Some(SweepDestination::CommunityPool),
+        claim_start_time: None,
+        screening_contract: None,
+        gas_pool_denom: None,
+        claim_bonus: None,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    assert_eq!(
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg.clone()),
+        Err(StdError::generic_err(
+            "the community pool only takes native denoms"
+        ))
+    );
+
+    msg.denom = "uluna".to_string();
+    msg.token_type = None;
+    msg.sweep_destination = Some(SweepDestination::Treasury {
+        address: "treasury0000".to_string(),
+    });
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // the change only takes effect after the delay
+    let msg = ExecuteMsg::UpdateConfig {
+        fee_refund: None,
+        enabled: None,
+        sweep_destination: Some(SweepDestination::Burn),
+        claim_end_time: None,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("admin0000", &[]), msg).unwrap();
+
+    let effective_time = mock_env().block.time.seconds() + 259200u64;
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        config.sweep_destination,
+        SweepDestination::Treasury {
+            address: "treasury0000".to_string(),
+        }
+    );
+    assert_eq!(
+        config.pending_sweep_destination,
+        Some(PendingSweep {
+            destination: SweepDestination::Burn,
+            effective_time,
+        })
+    );
+
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(1571800001u64);
+    let res = execute(
+        deps.as_mut(),
+        env,
+        mock_info("admin0000", &[]),
+        ExecuteMsg::End {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "treasury0000".to_string(),
+            amount: coins(1000, "uluna"),
+        }))]
+    );
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "end_airdrop"),
+            attr("destination", "treasury"),
+            attr("recipient", "treasury0000"),
+        ]
+    );
+
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(effective_time);
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(config.sweep_destination, SweepDestination::Burn);
+    assert_eq!(config.pending_sweep_destination, None);
+
+    let res = execute(
+        deps.as_mut(),
+        env,
+        mock_info("admin0000", &[]),
+        ExecuteMsg::End {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Burn {
+            amount: coins(1000, "uluna"),
+        }))]
+    );
+    assert_eq!(
+        res.attributes,
+        vec![attr("action", "end_airdrop"), attr("destination", "burn")]
+    );
+}
+
+#[test]
+fn pause_and_resume() {
+    let mut deps = mock_dependencies();
+
+    let msg = InstantiateMsg {
+        admin: "admin0000".to_string(),
+        denom: "uluna".to_string(),
+        vesting_periods: vec![],
+        start_time: None,
+        prefix: None,
+        claim_end_time: 1955870000u64,
+        fee_refund: None,
+        vesting_mode: None,
+        vesting_fallback: None,
+        cliff_seconds: None,
+        clawback_funder: None,
+        extra_denoms: None,
+        token_type: None,
+        sweep_destination: None,
+        claim_start_time: None,
+        screening_contract: None,
+        gas_pool_denom: None,
+        claim_bonus: None,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let msg = ExecuteMsg::GrantRole {
+        role: Role::Pauser,
+        address: "pauser0000".to_string(),
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("admin0000", &[]), msg).unwrap();
+
+    let now = mock_env().block.time.seconds();
+    let pause = |until| ExecuteMsg::Pause {
+        reason: "root mismatch".to_string(),
+        until,
+    };
+    assert_eq!(
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("addr0000", &[]),
+            pause(None)
+        ),
+        Err(StdError::generic_err("unauthorized"))
+    );
+    assert_eq!(
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("pauser0000", &[]),
+            pause(Some(now + 100))
+        ),
+        Err(StdError::generic_err("unauthorized"))
+    );
+    assert_eq!(
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin0000", &[]),
+            pause(Some(now))
+        ),
+        Err(StdError::generic_err("until must be in the future"))
+    );
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin0000", &[]),
+        pause(Some(now + 100)),
+    )
+    .unwrap();
+    assert_eq!(
+        res.events,
+        vec![Event::new("airdrop_paused")
+            .add_attribute("paused_by", "admin0000")
+            .add_attribute("reason", "root mismatch")
+            .add_attribute("until", (now + 100).to_string())]
+    );
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert!(!config.enabled);
+    assert_eq!(
+        config.pause,
+        Some(PauseInfo {
+            reason: "root mismatch".to_string(),
+            paused_at: now,
+            until: Some(now + 100),
+        })
+    );
+
+    let claim = ExecuteMsg::Claim {
+        allocation: "addr0000,1000".to_string(),
+        proofs: vec![],
+        message: "".to_string(),
+        signature: "".to_string(),
+        delegate_to: None,
+    };
+    let paused = Err(StdError::generic_err(format!(
+        "airdrop event is paused: root mismatch (resumes at {})",
+        now + 100
+    )));
+    assert_eq!(
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("addr0000", &[]),
+            claim.clone()
+        ),
+        paused
+    );
+
+    // claims reopen on their own at the scheduled time
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(now + 100);
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert!(config.enabled);
+    assert_eq!(config.pause, None);
+    assert_ne!(
+        execute(deps.as_mut(), env, mock_info("addr0000", &[]), claim),
+        paused
+    );
+
+    // only the admin resumes
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("pauser0000", &[]),
+        pause(None),
+    )
+    .unwrap();
+    assert_eq!(res.events[0].attributes.len(), 2);
+    assert_eq!(
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("pauser0000", &[]),
+            ExecuteMsg::Resume {}
+        ),
+        Err(StdError::generic_err("unauthorized"))
+    );
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(now + 60);
+    let res = execute(
+        deps.as_mut(),
+        env,
+        mock_info("admin0000", &[]),
+        ExecuteMsg::Resume {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.events,
+        vec![Event::new("airdrop_resumed")
+            .add_attribute("resumed_by", "admin0000")
+            .add_attribute("reason", "root mismatch")
+            .add_attribute("paused_seconds", "60")]
+    );
+    assert_eq!(
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin0000", &[]),
+            ExecuteMsg::Resume {}
+        ),
+        Err(StdError::generic_err("airdrop event is not paused"))
+    );
+}
+
+#[test]
+fn pauser_cannot_shorten_pause() {
+    let mut deps = mock_dependencies();
+
+    let msg = InstantiateMsg {
+        admin: "admin0000".to_string(),
+        denom: "uluna".to_string(),
+        vesting_periods: vec![],
+        start_time: None,
+        prefix: None,
+        claim_end_time: 1955870000u64,
+        fee_refund: None,
+        vesting_mode: None,
+        vesting_fallback: None,
+        cliff_seconds: None,
+        clawback_funder: None,
+        extra_denoms: None,
+        token_type: None,
+        sweep_destination: None,
+        claim_start_time: None,
+        screening_contract: None,
+        gas_pool_denom: None,
+        claim_bonus: None,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let msg = ExecuteMsg::GrantRole {
+        role: Role::Pauser,
+        address: "pauser0000".to_string(),
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("admin0000", &[]), msg).unwrap();
+
+    let msg = ExecuteMsg::Pause {
+        reason: "incident".to_string(),
+        until: None,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("admin0000", &[]), msg).unwrap();
+
+    // a pause ending a second later would reopen claims
+    let now = mock_env().block.time.seconds();
+    let msg = ExecuteMsg::Pause {
+        reason: "incident".to_string(),
+        until: Some(now + 1),
+    };
+    assert_eq!(
+        execute(deps.as_mut(), mock_env(), mock_info("pauser0000", &[]), msg),
+        Err(StdError::generic_err("unauthorized"))
+    );
+
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(now + 2);
+    let res = query(deps.as_ref(), env, QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert!(!config.enabled);
+    assert_eq!(
+        config.pause,
+        Some(PauseInfo {
+            reason: "incident".to_string(),
+            paused_at: now,
+            until: None,
+        })
+    );
+}
+
+#[test]
+fn claim_window() {
+    let mut deps = mock_dependencies();
+
+    let mut msg = InstantiateMsg {
+        admin: "admin0000".to_string(),
+        denom: "uluna".to_string(),
+        vesting_periods: vec![],
+        start_time: None,
+        prefix: None,
+        claim_end_time: 1955870000u64,
+        fee_refund: None,
+        vesting_mode: None,
+        vesting_fallback: None,
+        cliff_seconds: None,
+        clawback_funder: None,
+        extra_denoms: None,
+        token_type: None,
+        sweep_destination: None,
+        claim_start_time: Some(1955870000u64),
+        screening_contract: None,
+        gas_pool_denom: None,
+        claim_bonus: None,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    assert_eq!(
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg.clone()),
+        Err(StdError::generic_err(
+            "claim_start_time must be before claim_end_time"
+        ))
+    );
+
+    msg.claim_start_time = Some(1755870000u64);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let claim = ExecuteMsg::Claim {
+        allocation: "addr0000,1000,0,0,0,0".to_string(),
+        proofs: vec![],
+        message: "".to_string(),
+        signature: "".to_string(),
+        delegate_to: None,
+    };
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(1755869999u64);
+    assert_eq!(
+        execute(deps.as_mut(), env, mock_info("addr0000", &[]), claim),
+        Err(StdError::generic_err("airdrop event not started"))
+    );
+
+    // only the admin can move the end of the window
+    let update = |claim_end_time| ExecuteMsg::UpdateConfig {
+        fee_refund: None,
+        enabled: None,
+        sweep_destination: None,
+        claim_end_time: Some(claim_end_time),
+    };
+    assert_eq!(
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("addr0000", &[]),
+            update(1965870000u64)
+        ),
+        Err(StdError::generic_err("unauthorized"))
+    );
+    assert_eq!(
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin0000", &[]),
+            update(1955860000u64)
+        ),
+        Err(StdError::generic_err("claim_end_time can only be extended"))
+    );
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin0000", &[]),
+        update(1965870000u64),
+    )
+    .unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(config.claim_start_time, 1755870000u64);
+    assert_eq!(config.claim_end_time, 1965870000u64);
+
+    // an ended window can't be reopened
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(1965870001u64);
+    assert_eq!(
+        execute(
+            deps.as_mut(),
+            env,
+            mock_info("admin0000", &[]),
+            update(1975870000u64)
+        ),
+        Err(StdError::generic_err("airdrop event ended"))
+    );
+}
+
+#[cfg(feature = "terra")]
+#[test]
+fn claim_terra_escrow_and_withdraw() {
+    let mut deps = mock_dependencies_with_balance(&[Coin::new(100000u128, "uluna")]);
+
+    let msg = InstantiateMsg {
+        admin: "admin0000".to_string(),
+        denom: "uluna".to_string(),
+        vesting_periods: vec![1000i64, 1000i64, 1000i64],
+        start_time: Some(1571797419i64),
+        prefix: None,
+        claim_end_time: 1571800000u64,
+        fee_refund: None,
+        vesting_mode: Some(VestingMode::Escrow),
+        vesting_fallback: None,
+        cliff_seconds: None,
+        clawback_funder: None,
+        extra_denoms: None,
+        token_type: None,
+        sweep_destination: None,
+        claim_start_time: None,
+        screening_contract: None,
+        gas_pool_denom: None,
+        claim_bonus: None,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let allocation = "terra1dcegyrekltswvyy0xy69ydgxn9x8x32zdtapd8,100,1000,0,2000".to_string();
+    let info = mock_info("admin0000", &[]);
+    let msg = ExecuteMsg::RegisterMerkleRoot {
+        merkle_root: single_leaf_root(&allocation),
+        total_amount: None,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let msg = ExecuteMsg::Claim {
+        allocation,
+        proofs: vec![],
+        message: "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9".to_string(),
+        signature: "".to_string(),
+        delegate_to: None,
+    };
+    let info = mock_info("terra1dcegyrekltswvyy0xy69ydgxn9x8x32zdtapd8", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // only the liquid amount leaves the contract
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9".to_string(),
+            amount: coins(100, "uluna")
+        }))]
+    );
+    assert_eq!(res.attributes[4], attr("vesting", "3000"));
+
+    let vesting_status = |deps: cosmwasm_std::Deps, time: u64| -> VestingStatusResponse {
+        let mut env = mock_env();
+        env.block.time = Timestamp::from_seconds(time);
+        from_binary(
+            &query(
+                deps,
+                env,
+                QueryMsg::VestingStatus {
+                    address: "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9".to_string(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap()
+    };
+    assert_eq!(
+        vesting_status(deps.as_ref(), 1571798418u64),
+        VestingStatusResponse {
+            locked: Uint128::new(3000),
+            unlocked: Uint128::zero(),
+            withdrawn: Uint128::zero(),
+        }
+    );
+
+    // nothing unlocked yet
+    let info = mock_info("terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9", &[]);
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(1571798418u64);
+    assert_eq!(
+        execute(deps.as_mut(), env, info.clone(), ExecuteMsg::Withdraw {}),
+        Err(StdError::generic_err("nothing to withdraw"))
+    );
+
+    // first two tranches unlocked
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(1571799419u64);
+    let res = execute(deps.as_mut(), env, info.clone(), ExecuteMsg::Withdraw {}).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9".to_string(),
+            amount: coins(1000, "uluna")
+        }))]
+    );
+    assert_eq!(
+        vesting_status(deps.as_ref(), 1571799419u64),
+        VestingStatusResponse {
+            locked: Uint128::new(2000),
+            unlocked: Uint128::new(1000),
+            withdrawn: Uint128::new(1000),
+        }
+    );
+
+    // the end sweep leaves the remaining escrow in the contract
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(1571800001u64);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("admin0000", &[]),
+        ExecuteMsg::End {},
+    )
+    .unwrap();
+    let mut msg = MsgFundCommunityPool::new();
+    let mut coin = DistributionCoin::new();
+    coin.amount = "98000".to_string();
+    coin.denom = "uluna".to_string();
+    msg.amount = vec![coin];
+    msg.depositor = env.contract.address.to_string();
+    assert_eq!(
+        res.messages[0],
+        SubMsg::new(CosmosMsg::Stargate {
+            type_url: "/cosmos.distribution.v1beta1.MsgFundCommunityPool".to_string(),
+            value: Binary(Message::write_to_bytes(&msg).unwrap()),
+        })
+    );
+
+    // the rest can be withdrawn once the last tranche unlocks
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(1571800419u64);
+    let res = execute(deps.as_mut(), env, info.clone(), ExecuteMsg::Withdraw {}).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9".to_string(),
+            amount: coins(2000, "uluna")
+        }))]
+    );
+
+    assert_eq!(
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("terra1dcegyrekltswvyy0xy69ydgxn9x8x32zdtapd8", &[]),
+            ExecuteMsg::Withdraw {}
+        ),
+        Err(StdError::generic_err("no vesting schedule"))
+    );
+
+    // plain escrow schedules can't be clawed back
+    assert_eq!(
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin0000", &[]),
+            ExecuteMsg::Clawback {
+                recipient: "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9".to_string(),
+            }
+        ),
+        Err(StdError::generic_err("vesting schedule is not revocable"))
+    );
+}
+
+#[cfg(feature = "terra")]
+#[test]
+fn claim_terra_clawback() {
+    for clawback_funder in [None, Some("treasury0000".to_string())] {
+        let mut deps = mock_dependencies_with_balance(&[Coin::new(100000u128, "uluna")]);
+
+        let msg = InstantiateMsg {
+            admin: "admin0000".to_string(),
+            denom: "uluna".to_string(),
+            vesting_periods: vec![1000i64, 1000i64],
+            start_time: Some(1571797419i64),
+            prefix: None,
+            claim_end_time: 1571800000u64,
+            fee_refund: None,
+            vesting_mode: Some(VestingMode::Clawback),
+            vesting_fallback: None,
+            cliff_seconds: None,
+            clawback_funder: clawback_funder.clone(),
+            extra_denoms: None,
+            token_type: None,
+            sweep_destination: None,
+            claim_start_time: None,
+            screening_contract: None,
+            gas_pool_denom: None,
+            claim_bonus: None,
+        };
+
+        let info = mock_info("addr0000", &[]);
+        let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let allocation = "terra1dcegyrekltswvyy0xy69ydgxn9x8x32zdtapd8,100,1000,2000".to_string();
+        let info = mock_info("admin0000", &[]);
+        let msg = ExecuteMsg::RegisterMerkleRoot {
+            merkle_root: single_leaf_root(&allocation),
+            total_amount: None,
+        };
+        let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let msg = ExecuteMsg::Claim {
+            allocation,
+            proofs: vec![],
+            message: "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9".to_string(),
+            signature: "".to_string(),
+            delegate_to: None,
+        };
+        let info = mock_info("terra1dcegyrekltswvyy0xy69ydgxn9x8x32zdtapd8", &[]);
+        let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(res.messages.len(), 1);
+        assert_eq!(res.attributes[4], attr("vesting", "3000"));
+
+        // looked up the same way claim keyed the schedule
+        let clawback = ExecuteMsg::Clawback {
+            recipient: "TERRA1JH4TH9U5ZK4WA38WGTMXJMPSVWNSJEVJQAZ8H9".to_string(),
+        };
+        let mut env = mock_env();
+        env.block.time = Timestamp::from_seconds(1571798419u64);
+
+        // only the admin can claw back
+        assert_eq!(
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                mock_info("terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9", &[]),
+                clawback.clone()
+            ),
+            Err(StdError::generic_err("unauthorized"))
+        );
+
+        // the first tranche unlocked and stays with the recipient
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("admin0000", &[]),
+            clawback.clone(),
+        )
+        .unwrap();
+        let expected = match &clawback_funder {
+            Some(funder) => CosmosMsg::Bank(BankMsg::Send {
+                to_address: funder.clone(),
+                amount: coins(2000, "uluna"),
+            }),
+            None => {
+                let mut msg = MsgFundCommunityPool::new();
+                let mut coin = DistributionCoin::new();
+                coin.amount = "2000".to_string();
+                coin.denom = "uluna".to_string();
+                msg.amount = vec![coin];
+                msg.depositor = env.contract.address.to_string();
+                CosmosMsg::Stargate {
+                    type_url: "/cosmos.distribution.v1beta1.MsgFundCommunityPool".to_string(),
+                    value: Binary(Message::write_to_bytes(&msg).unwrap()),
+                }
+            }
+        };
+        assert_eq!(res.messages, vec![SubMsg::new(expected)]);
+        assert_eq!(
+            res.attributes,
+            vec![
+                attr("action", "clawback"),
+                attr("recipient", "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9"),
+                attr("amount", "2000"),
+                attr(
+                    "destination",
+                    clawback_funder
+                        .clone()
+                        .unwrap_or_else(|| "community_pool".to_string())
+                ),
+            ]
+        );
+
+        assert_eq!(
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                mock_info("admin0000", &[]),
+                clawback.clone()
+            ),
+            Err(StdError::generic_err("nothing to claw back"))
+        );
+
+        let res: VestingStatusResponse = from_binary(
+            &query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::VestingStatus {
+                    address: "TERRA1JH4TH9U5ZK4WA38WGTMXJMPSVWNSJEVJQAZ8H9".to_string(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            res,
+            VestingStatusResponse {
+                locked: Uint128::zero(),
+                unlocked: Uint128::new(1000),
+                withdrawn: Uint128::zero(),
+            }
+        );
+
+        let res = execute(
+            deps.as_mut(),
+            env,
+            mock_info("terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9", &[]),
+            ExecuteMsg::Withdraw {},
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9".to_string(),
+                amount: coins(1000, "uluna")
+            }))]
+        );
+    }
+}
+
+#[cfg(feature = "terra")]
+#[test]
+fn vesting_account_fallback() {
+    for vesting_fallback in [
+        VestingFallback::Escrow,
+        VestingFallback::Liquid,
+        VestingFallback::Revert,
+    ] {
+        let mut deps = mock_dependencies_with_balance(&coins(1_000_000_000, "uluna"));
+
+        let msg = InstantiateMsg {
+            admin: "admin0000".to_string(),
+            denom: "uluna".to_string(),
+            vesting_periods: vec![1000i64, 1000i64, 1000i64],
+            start_time: Some(1571797419i64),
+            prefix: None,
+            claim_end_time: 1955870000u64,
+            fee_refund: None,
+            vesting_mode: None,
+            vesting_fallback: Some(vesting_fallback.clone()),
+            cliff_seconds: None,
+            clawback_funder: None,
+            extra_denoms: None,
+            token_type: None,
+            sweep_destination: None,
+            claim_start_time: None,
+            screening_contract: None,
+            gas_pool_denom: None,
+            claim_bonus: None,
+        };
+
+        let info = mock_info("addr0000", &[]);
+        let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let allocation = "terra1dcegyrekltswvyy0xy69ydgxn9x8x32zdtapd8,100,1000,0,2000".to_string();
+        let info = mock_info("admin0000", &[]);
+        let msg = ExecuteMsg::RegisterMerkleRoot {
+            merkle_root: single_leaf_root(&allocation),
+            total_amount: None,
+        };
+        let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let msg = ExecuteMsg::Claim {
+            allocation,
+            proofs: vec![],
+            message: "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9".to_string(),
+            signature: "".to_string(),
+            delegate_to: None,
+        };
+        let info = mock_info("terra1dcegyrekltswvyy0xy69ydgxn9x8x32zdtapd8", &[]);
+        let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let res = reply(
+            deps.as_mut(),
+            mock_env(),
+            Reply {
+                id: CREATE_VESTING_ACCOUNT_REPLY_ID,
+                result: SubMsgResult::Err("account already exists".to_string()),
+            },
+        );
+
+        let event = |policy: &str| {
+            Event::new("vesting_fallback")
+                .add_attribute("recipient", "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9")
+                .add_attribute("policy", policy)
+                .add_attribute("reason", "account already exists")
+        };
+        let query_status = |deps: cosmwasm_std::Deps| -> VestingStatusResponse {
+            from_binary(
+                &query(
+                    deps,
+                    mock_env(),
+                    QueryMsg::VestingStatus {
+                        address: "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9".to_string(),
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap()
+        };
+        match vesting_fallback {
+            VestingFallback::Escrow => {
+                let res = res.unwrap();
+                assert!(res.messages.is_empty());
+                assert_eq!(res.events, vec![event("escrow")]);
+                assert_eq!(query_status(deps.as_ref()).locked, Uint128::new(3000));
+            }
+            VestingFallback::Liquid => {
+                let res = res.unwrap();
+                assert_eq!(
+                    res.messages,
+                    vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                        to_address: "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9".to_string(),
+                        amount: coins(3000, "uluna")
+                    }))]
+                );
+                assert_eq!(res.events, vec![event("liquid")]);
+                assert_eq!(query_status(deps.as_ref()).locked, Uint128::zero());
+            }
+            VestingFallback::Revert => {
+                assert_eq!(
+                    res,
+                    Err(StdError::generic_err(
+                        "unable to create vesting account: account already exists"
+                    ))
+                );
+            }
+        }
+        assert_eq!(deps.storage.get(b"pending_vesting"), None);
+    }
+
+    let mut deps = mock_dependencies();
+    assert_eq!(
+        reply(
+            deps.as_mut(),
+            mock_env(),
+            Reply {
+                id: 100,
+                result: SubMsgResult::Err("".to_string()),
+            },
+        ),
+        Err(StdError::generic_err("unknown reply id: 100"))
+    );
+}
+
+#[cfg(feature = "terra")]
+#[test]
+fn vesting_account_created() {
+    let mut deps = mock_dependencies_with_balance(&coins(1_000_000_000, "uluna"));
+
+    let msg = InstantiateMsg {
+        admin: "admin0000".to_string(),
+        denom: "uluna".to_string(),
+        vesting_periods: vec![1000i64, 1000i64, 1000i64],
+        start_time: Some(1571797419i64),
+        prefix: None,
+        claim_end_time: 1955870000u64,
+        fee_refund: None,
+        vesting_mode: None,
+        vesting_fallback: None,
+        cliff_seconds: None,
+        clawback_funder: None,
+        extra_denoms: None,
+        token_type: None,
+        sweep_destination: None,
+        claim_start_time: None,
+        screening_contract: None,
+        gas_pool_denom: None,
+        claim_bonus: None,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // nothing is kept when the claim creates no vesting account
+    let allocation = "terra1dcegyrekltswvyy0xy69ydgxn9x8x32zdtapd8,100,0,0,0".to_string();
+    let info = mock_info("admin0000", &[]);
+    let msg = ExecuteMsg::RegisterMerkleRoot {
+        merkle_root: single_leaf_root(&allocation),
+        total_amount: None,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    let msg = ExecuteMsg::Claim {
+        allocation,
+        proofs: vec![],
+        message: "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9".to_string(),
+        signature: "".to_string(),
+        delegate_to: None,
+    };
+    let info = mock_info("terra1dcegyrekltswvyy0xy69ydgxn9x8x32zdtapd8", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(deps.storage.get(b"pending_vesting"), None);
+
+    // and the schedule is dropped once the account was created
+    let allocation = "terra1qfqa2eu9wp272ha93lj4yhcenrc6ymng079nu8,100,1000,0,2000".to_string();
+    let info = mock_info("admin0000", &[]);
+    let msg = ExecuteMsg::RegisterMerkleRoot {
+        merkle_root: single_leaf_root(&allocation),
+        total_amount: None,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    let msg = ExecuteMsg::Claim {
+        allocation,
+        proofs: vec![],
+        message: "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9".to_string(),
+        signature: "".to_string(),
+        delegate_to: None,
+    };
+    let info = mock_info("terra1qfqa2eu9wp272ha93lj4yhcenrc6ymng079nu8", &[]);
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert!(deps.storage.get(b"pending_vesting").is_some());
+
+    let res = reply(
+        deps.as_mut(),
+        mock_env(),
+        Reply {
+            id: CREATE_VESTING_ACCOUNT_REPLY_ID,
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: vec![],
+                data: None,
+            }),
+        },
+    )
+    .unwrap();
+    assert_eq!(res, Response::default());
+    assert_eq!(deps.storage.get(b"pending_vesting"), None);
+}
+
+#[cfg(feature = "terra")]
+#[test]
+fn vesting_account_fallback_delayed() {
+    let mut deps = mock_dependencies_with_balance(&coins(1_000_000_000, "uluna"));
+
+    let msg = InstantiateMsg {
+        admin: "admin0000".to_string(),
+        denom: "uluna".to_string(),
+        vesting_periods: vec![1000i64, 1000i64, 1000i64],
+        start_time: Some(1571797419i64),
+        prefix: None,
+        claim_end_time: 1955870000u64,
+        fee_refund: None,
+        vesting_mode: Some(VestingMode::Delayed),
+        vesting_fallback: Some(VestingFallback::Escrow),
+        cliff_seconds: None,
+        clawback_funder: None,
+        extra_denoms: None,
+        token_type: None,
+        sweep_destination: None,
+        claim_start_time: None,
+        screening_contract: None,
+        gas_pool_denom: None,
+        claim_bonus: None,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let allocation = "terra1dcegyrekltswvyy0xy69ydgxn9x8x32zdtapd8,100,1000,0,2000".to_string();
+    let info = mock_info("admin0000", &[]);
+    let msg = ExecuteMsg::RegisterMerkleRoot {
+        merkle_root: single_leaf_root(&allocation),
+        total_amount: None,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let msg = ExecuteMsg::Claim {
+        allocation,
+        proofs: vec![],
+        message: "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9".to_string(),
+        signature: "".to_string(),
+        delegate_to: None,
+    };
+    let info = mock_info("terra1dcegyrekltswvyy0xy69ydgxn9x8x32zdtapd8", &[]);
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let _res = reply(
+        deps.as_mut(),
+        mock_env(),
+        Reply {
+            id: CREATE_VESTING_ACCOUNT_REPLY_ID,
+            result: SubMsgResult::Err("account already exists".to_string()),
+        },
+    )
+    .unwrap();
+
+    // like the delayed account, escrow releases nothing before the last period ends
+    let query_status = |time: u64| -> VestingStatusResponse {
+        let mut env = mock_env();
+        env.block.time = Timestamp::from_seconds(time);
+        from_binary(
+            &query(
+                deps.as_ref(),
+                env,
+                QueryMsg::VestingStatus {
+                    address: "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9".to_string(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap()
+    };
+    assert_eq!(query_status(1571798419u64).unlocked, Uint128::zero());
+    assert_eq!(query_status(1571800418u64).unlocked, Uint128::zero());
+    assert_eq!(query_status(1571800419u64).unlocked, Uint128::new(3000));
+}
+
+#[cfg(feature = "terra")]
+#[test]
+fn claim_terra_with_cliff() {
+    let mut deps = mock_dependencies_with_balance(&coins(1_000_000_000, "uluna"));
+
+    let msg = InstantiateMsg {
+        admin: "admin0000".to_string(),
+        denom: "uluna".to_string(),
+        vesting_periods: vec![2592000i64; 3],
+        start_time: Some(1655360550i64),
+        prefix: None,
+        claim_end_time: 1955870000u64,
+        fee_refund: None,
+        vesting_mode: None,
+        vesting_fallback: None,
+        cliff_seconds: Some(15552000u64),
+        clawback_funder: None,
+        extra_denoms: None,
+        token_type: None,
+        sweep_destination: None,
+        claim_start_time: None,
+        screening_contract: None,
+        gas_pool_denom: None,
+        claim_bonus: None,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let env = mock_env();
+    let _res = instantiate(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(config.cliff_seconds, 15552000u64);
+
+    let allocation = "terra1dcegyrekltswvyy0xy69ydgxn9x8x32zdtapd8,0,1000,1000,1000".to_string();
+    let info = mock_info("admin0000", &[]);
+    let msg = ExecuteMsg::RegisterMerkleRoot {
+        merkle_root: single_leaf_root(&allocation),
+        total_amount: None,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let msg = ExecuteMsg::Claim {
+        allocation,
+        proofs: vec![],
+        message: "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9".to_string(),
+        signature: "".to_string(),
+        delegate_to: None,
+    };
+    let info = mock_info("terra1dcegyrekltswvyy0xy69ydgxn9x8x32zdtapd8", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // the cliff is folded into the first period
+    let mut vesting_msg = MsgCreatePeriodicVestingAccount::new();
+    vesting_msg.from_address = env.contract.address.to_string();
+    vesting_msg.to_address = "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9".to_string();
+    vesting_msg.start_time = 1655360550i64;
+    vesting_msg.vesting_periods = [
+        (18144000i64, "1000".to_string()),
+        (2592000i64, "1000".to_string()),
+        (2592000i64, "1000".to_string()),
+    ]
+    .iter()
+    .map(to_period)
+    .collect::<Vec<Period>>();
+
+    let bytes = Message::write_to_bytes(&vesting_msg).unwrap();
+
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::reply_always(
+            CosmosMsg::Stargate {
+                type_url: "/cosmos.vesting.v1beta1.MsgCreatePeriodicVestingAccount".to_string(),
+                value: Binary(bytes),
+            },
+            CREATE_VESTING_ACCOUNT_REPLY_ID,
+        )]
+    );
+}
+
+#[cfg(feature = "terra")]
+#[test]
+fn claim_terra_with_leaf_schedules() {
+    let mut deps = mock_dependencies_with_balance(&coins(1_000_000_000, "uluna"));
+
+    let msg = InstantiateMsg {
+        admin: "admin0000".to_string(),
+        denom: "uluna".to_string(),
+        vesting_periods: vec![2592000i64; 3],
+        start_time: Some(1655360550i64),
+        prefix: None,
+        claim_end_time: 1955870000u64,
+        fee_refund: None,
+        vesting_mode: None,
+        vesting_fallback: None,
+        cliff_seconds: None,
+        clawback_funder: None,
+        extra_denoms: None,
+        token_type: None,
+        sweep_destination: None,
+        claim_start_time: None,
+        screening_contract: None,
+        gas_pool_denom: None,
+        claim_bonus: None,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let env = mock_env();
+    let _res = instantiate(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    // register a schedule for the team cohort
+    let msg = ExecuteMsg::RegisterSchedule {
+        id: "team".to_string(),
+        periods: vec![31536000i64, 31536000i64],
+        cliff_seconds: Some(15552000u64),
+    };
+    assert_eq!(
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("addr0000", &[]),
+            msg.clone()
+        ),
+        Err(StdError::generic_err("unauthorized"))
+    );
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin0000", &[]),
+        msg.clone(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![attr("action", "register_schedule"), attr("id", "team")]
+    );
+    assert_eq!(
+        execute(deps.as_mut(), mock_env(), mock_info("admin0000", &[]), msg),
+        Err(StdError::generic_err("schedule already registered"))
+    );
+    assert_eq!(
+        from_binary::<ScheduleResponse>(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Schedule {
+                    id: "team".to_string()
+                }
+            )
+            .unwrap()
+        )
+        .unwrap(),
+        ScheduleResponse {
+            id: "team".to_string(),
+            periods: vec![31536000i64, 31536000i64],
+            cliff_seconds: 15552000u64,
+        }
+    );
+
+    let claim = |deps: &mut cosmwasm_std::OwnedDeps<_, _, _>, signer: &str, allocation: &str| {
+        let info = mock_info("admin0000", &[]);
+        let msg = ExecuteMsg::RegisterMerkleRoot {
+            merkle_root: single_leaf_root(allocation),
+            total_amount: None,
+        };
+        let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let msg = ExecuteMsg::Claim {
+            allocation: allocation.to_string(),
+            proofs: vec![],
+            message: "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9".to_string(),
+            signature: "".to_string(),
+            delegate_to: None,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info(signer, &[]), msg)
+    };
+    let expected_message = |periods: &[(i64, String)]| {
+        let mut vesting_msg = MsgCreatePeriodicVestingAccount::new();
+        vesting_msg.from_address = env.contract.address.to_string();
+        vesting_msg.to_address = "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9".to_string();
+        vesting_msg.start_time = 1655360550i64;
+        vesting_msg.vesting_periods = periods.iter().map(to_period).collect::<Vec<Period>>();
+        SubMsg::reply_always(
+            CosmosMsg::Stargate {
+                type_url: "/cosmos.vesting.v1beta1.MsgCreatePeriodicVestingAccount".to_string(),
+                value: Binary(Message::write_to_bytes(&vesting_msg).unwrap()),
+            },
+            CREATE_VESTING_ACCOUNT_REPLY_ID,
+        )
+    };
+
+    // registered schedule, with its own cliff
+    let res = claim(
+        &mut deps,
+        "terra1dcegyrekltswvyy0xy69ydgxn9x8x32zdtapd8",
+        "terra1dcegyrekltswvyy0xy69ydgxn9x8x32zdtapd8,0,@team,1000,2000",
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![expected_message(&[
+            (47088000i64, "1000".to_string()),
+            (31536000i64, "2000".to_string()),
+        ])]
+    );
+
+    // period lengths carried in the leaf
+    let res = claim(
+        &mut deps,
+        "terra1zdpgj8am5nqqvht927k3etljyl6a52kwqup0je",
+        "terra1zdpgj8am5nqqvht927k3etljyl6a52kwqup0je,0,100:1000,200:2000,300:3000,400:4000",
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![expected_message(&[
+            (100i64, "1000".to_string()),
+            (200i64, "2000".to_string()),
+            (300i64, "3000".to_string()),
+            (400i64, "4000".to_string()),
+        ])]
+    );
+
+    assert_eq!(
+        claim(
+            &mut deps,
+            "terra1qfqa2eu9wp272ha93lj4yhcenrc6ymng079nu8",
+            "terra1qfqa2eu9wp272ha93lj4yhcenrc6ymng079nu8,0,@advisors,1000,2000",
+        ),
+        Err(StdError::generic_err("unknown vesting schedule: advisors"))
+    );
+    assert_eq!(
+        claim(
+            &mut deps,
+            "terra1qfqa2eu9wp272ha93lj4yhcenrc6ymng079nu8",
+            "terra1qfqa2eu9wp272ha93lj4yhcenrc6ymng079nu8,0,100:1000,2000",
+        ),
+        Err(StdError::generic_err("unable to parse vesting period 2"))
+    );
+    assert_eq!(
+        claim(
+            &mut deps,
+            "terra1qfqa2eu9wp272ha93lj4yhcenrc6ymng079nu8",
+            "terra1qfqa2eu9wp272ha93lj4yhcenrc6ymng079nu8,0,@team,1000",
+        ),
+        Err(StdError::generic_err("unable to parse claim amount 2"))
+    );
+}
+
+#[cfg(feature = "terra")]
+#[test]
+fn simulate_claim_terra() {
+    let mut deps = mock_dependencies_with_balance(&coins(1_000_000_000, "uluna"));
+
+    let msg = InstantiateMsg {
+        admin: "admin0000".to_string(),
+        denom: "uluna".to_string(),
+        vesting_periods: vec![1000i64, 1000i64, 1000i64],
+        start_time: Some(1655360550i64),
+        prefix: None,
+        claim_end_time: 1955870000u64,
+        fee_refund: Some(Uint128::new(150)),
+        vesting_mode: None,
+        vesting_fallback: None,
+        cliff_seconds: Some(500u64),
+        clawback_funder: None,
+        extra_denoms: None,
+        token_type: None,
+        sweep_destination: None,
+        claim_start_time: None,
+        screening_contract: None,
+        gas_pool_denom: None,
+        claim_bonus: None,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let allocation = "terra1dcegyrekltswvyy0xy69ydgxn9x8x32zdtapd8,100,1000,0,2000".to_string();
+    let info = mock_info("admin0000", &[]);
+    let msg = ExecuteMsg::RegisterMerkleRoot {
+        merkle_root: single_leaf_root(&allocation),
+        total_amount: None,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let simulate = |deps: cosmwasm_std::Deps, sender: &str| -> SimulateClaimResponse {
+        from_binary(
+            &query(
+                deps,
+                mock_env(),
+                QueryMsg::SimulateClaim {
+                    allocation: allocation.clone(),
+                    proofs: vec![],
+                    message: "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9".to_string(),
+                    signature: "".to_string(),
+                    sender: sender.to_string(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap()
+    };
+
+    // the fee is taken from the first tranche as amount0 is too small
+    assert_eq!(
+        simulate(
+            deps.as_ref(),
+            "terra1dcegyrekltswvyy0xy69ydgxn9x8x32zdtapd8"
+        ),
+        SimulateClaimResponse {
+            recipient: Some("terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9".to_string()),
+            liquid: coins(100, "uluna"),
+            vesting: vec![
+                VestingPeriodResponse {
+                    unlock_time: 1655362050u64,
+                    amount: coins(850, "uluna"),
+                },
+                VestingPeriodResponse {
+                    unlock_time: 1655363050u64,
+                    amount: vec![],
+                },
+                VestingPeriodResponse {
+                    unlock_time: 1655364050u64,
+                    amount: coins(2000, "uluna"),
+                },
+            ],
+            fee_refund: Uint128::new(150),
+            bonus: Uint128::zero(),
+            error: None,
+        }
+    );
+
+    assert_eq!(
+        simulate(deps.as_ref(), "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9"),
+        SimulateClaimResponse {
+            recipient: None,
+            liquid: vec![],
+            vesting: vec![],
+            fee_refund: Uint128::zero(),
+            bonus: Uint128::zero(),
+            error: Some("signer address does not match claim. Expected: terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9 Received: terra1dcegyrekltswvyy0xy69ydgxn9x8x32zdtapd8".to_string()),
+        }
+    );
+
+    // simulating does not mark the allocation as claimed
+    assert!(
+        !from_binary::<IsClaimedResponse>(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::IsClaimed {
+                    address: "terra1dcegyrekltswvyy0xy69ydgxn9x8x32zdtapd8".to_string(),
+                }
+            )
+            .unwrap()
+        )
+        .unwrap()
+        .is_claimed
+    );
+
+    let msg = ExecuteMsg::Claim {
+        allocation: allocation.clone(),
+        proofs: vec![],
+        message: "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9".to_string(),
+        signature: "".to_string(),
+        delegate_to: None,
+    };
+    let info = mock_info("terra1dcegyrekltswvyy0xy69ydgxn9x8x32zdtapd8", &[]);
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    assert_eq!(
+        simulate(
+            deps.as_ref(),
+            "terra1dcegyrekltswvyy0xy69ydgxn9x8x32zdtapd8"
+        )
+        .error,
+        Some("already claimed".to_string())
+    );
+}
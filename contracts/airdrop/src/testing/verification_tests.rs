@@ -14,7 +14,7 @@ fn verify_correct_eth_signature() {
 
     let deps = setup();
     let verified = verify_signature_eth(deps.as_ref(), message, signature, signer_address).unwrap();
-    assert_eq!(verified, true);
+    assert!(verified);
 }
 
 #[test]
@@ -25,7 +25,7 @@ fn verify_correct_eth_ledger_signature() {
 
     let deps = setup();
     let verified = verify_signature_eth(deps.as_ref(), message, signature, signer_address).unwrap();
-    assert_eq!(verified, true);
+    assert!(verified);
 }
 
 #[test]
@@ -36,7 +36,7 @@ fn verify_wrong_eth_message() {
 
     let deps = setup();
     let verified = verify_signature_eth(deps.as_ref(), message, signature, signer_address).unwrap();
-    assert_eq!(verified, false);
+    assert!(!verified);
 }
 
 #[test]
@@ -47,7 +47,7 @@ fn verify_wrong_eth_signer() {
 
     let deps = setup();
     let verified = verify_signature_eth(deps.as_ref(), message, signature, signer_address).unwrap();
-    assert_eq!(verified, false);
+    assert!(!verified);
 }
 
 #[test]
@@ -59,7 +59,7 @@ fn verify_correct_solana_signature() {
     let deps = setup();
     let verified =
         verify_signature_solana(deps.as_ref(), message, signature, signer_address).unwrap();
-    assert_eq!(verified, true);
+    assert!(verified);
 }
 
 #[test]
@@ -71,7 +71,7 @@ fn verify_wrong_solana_message() {
     let deps = setup();
     let verified =
         verify_signature_solana(deps.as_ref(), message, signature, signer_address).unwrap();
-    assert_eq!(verified, false);
+    assert!(!verified);
 }
 
 #[test]
@@ -83,7 +83,7 @@ fn verify_wrong_solana_signer() {
     let deps = setup();
     let verified =
         verify_signature_solana(deps.as_ref(), message, signature, signer_address).unwrap();
-    assert_eq!(verified, false);
+    assert!(!verified);
 }
 
 #[test]
@@ -95,7 +95,7 @@ fn verify_correct_cosmos_signer() {
     let deps = setup();
     let verified =
         verify_signature_cosmos(deps.as_ref(), message, signature, signer_address, "kava").unwrap();
-    assert_eq!(verified, true);
+    assert!(verified);
 
     let signer_address = "kava1myp8uav2hazdw79ldvruc96wcdf74dekva9qqu";
     let message = "terra1jh4th9u5zk4wa38wgtmxjmpsvwnsjevjqaz8h9";
@@ -104,7 +104,7 @@ fn verify_correct_cosmos_signer() {
     let deps = setup();
     let verified =
         verify_signature_cosmos(deps.as_ref(), message, signature, signer_address, "kava").unwrap();
-    assert_eq!(verified, true);
+    assert!(verified);
 }
 
 #[test]
@@ -117,7 +117,7 @@ fn verify_wrong_cosmos_signer() {
     let verified =
         verify_signature_cosmos(deps.as_ref(), message, signature, signer_address, "terra")
             .unwrap();
-    assert_eq!(verified, false);
+    assert!(!verified);
 }
 
 #[test]
@@ -130,5 +130,5 @@ fn verify_wrong_cosmos_message() {
     let verified =
         verify_signature_cosmos(deps.as_ref(), message, signature, signer_address, "terra")
             .unwrap();
-    assert_eq!(verified, false);
+    assert!(!verified);
 }
@@ -19,9 +19,7 @@ pub fn verify_signature(
 ) -> StdResult<String> {
     let verified = verify_signature_eth(deps, &message, &signature, &signer_address)?;
     if !verified {
-        return Err(StdError::generic_err(&format!(
-            "signature verification error",
-        )));
+        return Err(StdError::generic_err("signature verification error"));
     }
     Ok(message)
 }
@@ -36,9 +34,7 @@ pub fn verify_signature(
 ) -> StdResult<String> {
     let verified = verify_signature_solana(deps, &message, &signature, &signer_address)?;
     if !verified {
-        return Err(StdError::generic_err(&format!(
-            "signature verification error",
-        )));
+        return Err(StdError::generic_err("signature verification error"));
     }
     Ok(message)
 }
@@ -54,7 +50,7 @@ pub fn verify_signature(
     // No signature for terra
     let verified = verify_terra(sender.clone(), signer_address.clone());
     if !verified {
-        return Err(StdError::generic_err(&format!(
+        return Err(StdError::generic_err(format!(
             "signer address does not match claim. Expected: {} Received: {}",
             sender, signer_address
         )));
@@ -77,9 +73,7 @@ pub fn verify_signature(
     }?;
     let verified = verify_signature_cosmos(deps, &message, &signature, &signer_address, &prefix)?;
     if !verified {
-        return Err(StdError::generic_err(&format!(
-            "signature verification error",
-        )));
+        return Err(StdError::generic_err("signature verification error"));
     }
     Ok(message)
 }
@@ -170,5 +164,5 @@ pub fn verify_signature_cosmos(
 }
 
 pub fn verify_terra(sender: String, signer: String) -> bool {
-    return sender.eq(&signer);
+    sender.eq(&signer)
 }